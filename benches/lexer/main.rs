@@ -0,0 +1,116 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Benchmark del lexer sobre un programa generado grande. Compara el lexer
+// original (`Vec<char>` y un `String` por lexema) con el lexer actual en sus
+// tres modos: lexemas prestados de un `&str`, lexemas propios desde un `String`
+// y lectura incremental desde un `BufRead`.
+//
+//   cargo bench --bench lexer
+
+mod legacy;
+
+use std::hint::black_box;
+use std::io::BufReader;
+use std::time::{Duration, Instant};
+
+use compilador_rust::lexer::Lexer;
+use compilador_rust::token::TokenType;
+
+const REPETITIONS: usize = 20_000;
+const RUNS: usize = 5;
+
+const PROGRAM: &str = "let
+  const max ~ 100;
+  var total : Integer;
+  var c : Char;
+  func square (n : Integer) : Integer ~ n * n
+in
+  begin
+    total := 0; ! acumulador
+    while total <= max do
+      total := total + square(2) - 1;
+    if total >= max /\\ \\(total = 0) then c := 'x' else c := '\\n'
+  end
+";
+
+fn generate_program() -> String {
+    let mut source = String::with_capacity(PROGRAM.len() * REPETITIONS);
+    for _ in 0..REPETITIONS {
+        source.push_str(PROGRAM);
+    }
+    source
+}
+
+// Mejor tiempo de varias corridas; devuelve también la cantidad de tokens
+fn measure(mut run: impl FnMut() -> usize) -> (Duration, usize) {
+    let mut best = Duration::MAX;
+    let mut tokens = 0;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        tokens = black_box(run());
+        best = best.min(start.elapsed());
+    }
+    (best, tokens)
+}
+
+fn report(name: &str, bytes: usize, (time, tokens): (Duration, usize)) {
+    let seconds = time.as_secs_f64();
+    println!(
+        "{:<22} {:>10.2} ms {:>10.1} MB/s {:>12.0} tokens/s ({} tokens)",
+        name,
+        seconds * 1000.0,
+        bytes as f64 / seconds / 1_000_000.0,
+        tokens as f64 / seconds,
+        tokens
+    );
+}
+
+fn main() {
+    let source = generate_program();
+    let bytes = source.len();
+    println!("programa generado: {} bytes", bytes);
+
+    report(
+        "original (Vec<char>)",
+        bytes,
+        measure(|| {
+            let mut lexer = legacy::Lexer::new(source.clone());
+            let mut count = 1;
+            while lexer.next_token().token_type != legacy::TokenType::EOF {
+                count += 1;
+            }
+            count
+        }),
+    );
+
+    report(
+        "String (propios)",
+        bytes,
+        measure(|| Lexer::new(source.clone()).map(black_box).count()),
+    );
+
+    report(
+        "&str (prestados)",
+        bytes,
+        measure(|| Lexer::new(source.as_str()).map(black_box).count()),
+    );
+
+    report(
+        "BufRead",
+        bytes,
+        measure(|| {
+            Lexer::from_reader(BufReader::new(source.as_bytes()))
+                .map(black_box)
+                .count()
+        }),
+    );
+
+    // Los tres modos deben producir los mismos tokens
+    let borrowed: Vec<_> = Lexer::new(source.as_str()).collect();
+    let owned: Vec<_> = Lexer::new(source.clone()).collect();
+    assert_eq!(borrowed, owned);
+    assert_eq!(borrowed.last().map(|token| &token.token_type), Some(&TokenType::EOF));
+}
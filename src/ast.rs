@@ -0,0 +1,51 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+use crate::token::TokenType;
+
+#[derive(Debug, PartialEq)]
+pub enum ASTNode {
+    Let(Box<ASTNode>, Box<ASTNode>),
+    Const(String, Box<ASTNode>),
+    Var(String, TypeDenoter),
+    Func(String, Vec<ASTNode>, TypeDenoter, Box<ASTNode>),
+    // `func op (a: T, b: T) : R ~ e` y `func op (a: T) : R ~ e`
+    BinaryOperatorDeclaration(String, Box<ASTNode>, Box<ASTNode>, TypeDenoter, Box<ASTNode>),
+    UnaryOperatorDeclaration(String, Box<ASTNode>, TypeDenoter, Box<ASTNode>),
+    Proc(String, Vec<ASTNode>, Box<ASTNode>),
+    // Parámetros formales: `n: T` (por valor) y `var n: T` (por referencia)
+    ValueParameter(String, TypeDenoter),
+    VarParameter(String, TypeDenoter),
+    Type(String, TypeDenoter),
+    Assign(Vname, Box<ASTNode>),
+    If(Box<ASTNode>, TokenType, Box<ASTNode>, TokenType, Box<ASTNode>),
+    While(Box<ASTNode>, Box<ASTNode>),
+    Call(String, Vec<ASTNode>),
+    Expression(Box<ASTNode>),
+    Vname(Vname),
+    Number(i64),
+    Char(char),
+    ArrayAggregate(Vec<ASTNode>),
+    RecordAggregate(Vec<(String, ASTNode)>),
+    Operator(String, Box<ASTNode>, Box<ASTNode>),
+    UnaryOperator(String, Box<ASTNode>),
+    Declaration(Vec<ASTNode>),
+    Command(Vec<ASTNode>),
+}
+
+// Tipos: un nombre (`Integer`), `array n of T` o `record x: T, ... end`
+#[derive(Debug, PartialEq)]
+pub enum TypeDenoter {
+    Named(String),
+    Array(i64, Box<TypeDenoter>),
+    Record(Vec<(String, TypeDenoter)>),
+}
+
+// Nombres de valores o variables: `v`, `v.campo`, `v[i]` y sus combinaciones
+#[derive(Debug, PartialEq)]
+pub enum Vname {
+    Simple(String),
+    Field(Box<Vname>, String),
+    Index(Box<Vname>, Box<ASTNode>),
+}
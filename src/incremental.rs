@@ -0,0 +1,157 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Re-análisis léxico incremental: dado el vector de tokens anterior y una
+// edición del texto, solo se vuelve a lexear la región afectada. El análisis
+// empieza en el último límite entre tokens antes de la línea editada y se detiene en el
+// primer token, posterior a la edición, que coincide con uno de los tokens
+// anteriores; de ahí en adelante se reutilizan los tokens viejos desplazados.
+
+use std::ops::Range;
+
+use crate::lexer::{LexError, Lexer};
+use crate::token::Token;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    // Rango en bytes del texto anterior que se reemplaza
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, replacement: impl Into<String>) -> Self {
+        TextEdit {
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    // Aplica la edición a un texto
+    pub fn apply(&self, source: &str) -> String {
+        let mut edited = String::with_capacity(source.len() + self.replacement.len());
+        edited.push_str(&source[..self.range.start]);
+        edited.push_str(&self.replacement);
+        edited.push_str(&source[self.range.end..]);
+        edited
+    }
+
+    // Cambio en la longitud del texto
+    fn delta(&self) -> isize {
+        self.replacement.len() as isize - self.range.len() as isize
+    }
+}
+
+#[derive(Debug)]
+pub struct Relexed<'a> {
+    pub tokens: Vec<Token<'a>>,
+    // Índices de los tokens nuevos (en `tokens`) que reemplazan a los
+    // tokens `replaced` del vector anterior; el resto no cambió
+    pub changed: Range<usize>,
+    pub replaced: Range<usize>,
+    // Errores léxicos de la región re-analizada
+    pub errors: Vec<LexError>,
+}
+
+// `source` es el texto ya editado y `tokens` los tokens del texto anterior
+pub fn relex<'a>(tokens: &[Token], source: &'a str, edit: &TextEdit) -> Relexed<'a> {
+    relex_with(tokens, source, edit, |lexer| lexer)
+}
+
+// Igual que `relex`, con un lexer configurado como el que produjo `tokens`
+// (dialecto, trivia, etc.)
+pub fn relex_with<'a>(
+    tokens: &[Token],
+    source: &'a str,
+    edit: &TextEdit,
+    configure: impl FnOnce(Lexer<'a>) -> Lexer<'a>,
+) -> Relexed<'a> {
+    let delta = edit.delta();
+    let edit_end = (edit.range.start as isize + edit.replacement.len() as isize) as usize;
+
+    // Se vuelve a lexear desde el inicio de la línea editada: la edición puede
+    // unir un token con el anterior (`:` + `=`) y un literal de carácter sin
+    // cerrar depende del resto de su línea
+    let line_start = source[..edit.range.start].rfind('\n').map_or(0, |i| i + 1);
+    let first = tokens
+        .iter()
+        .position(|token| token.span.end >= line_start)
+        .unwrap_or(tokens.len());
+    let restart = if first == 0 { 0 } else { tokens[first - 1].span.end };
+
+    let mut lexer = configure(Lexer::new(source));
+    lexer.seek(restart);
+
+    let mut relexed: Vec<Token<'a>> = tokens[..first]
+        .iter()
+        .map(|token| token.clone().into_owned())
+        .collect();
+    let mut old = first;
+    let mut resync = None;
+
+    for token in lexer.by_ref() {
+        // Tokens viejos que quedaron antes del token nuevo ya no existen
+        while old < tokens.len() && shift(tokens[old].span.start, delta) < token.span.start as isize {
+            old += 1;
+        }
+        let matches_old = token.span.start >= edit_end
+            && old < tokens.len()
+            && tokens[old].token_type == token.token_type
+            && tokens[old].lexeme == token.lexeme
+            && shift(tokens[old].span.start, delta) == token.span.start as isize
+            && shift(tokens[old].span.end, delta) == token.span.end as isize;
+        relexed.push(token);
+        if matches_old {
+            resync = Some(old);
+            break;
+        }
+    }
+
+    let (changed, replaced) = match resync {
+        Some(old) => {
+            // El token de sincronización se re-analizó, pero es igual al viejo
+            let changed = first..relexed.len() - 1;
+            let tail = reuse_tail(&tokens[old + 1..], &relexed[changed.end], &tokens[old], delta);
+            relexed.extend(tail);
+            (changed, first..old)
+        }
+        None => (first..relexed.len(), first..tokens.len()),
+    };
+
+    Relexed {
+        tokens: relexed,
+        changed,
+        replaced,
+        errors: lexer.errors().to_vec(),
+    }
+}
+
+fn shift(offset: usize, delta: isize) -> isize {
+    offset as isize + delta
+}
+
+// Tokens posteriores al de sincronización, desplazados a su nueva posición.
+// Los que están en la misma línea que el token de sincronización cambian de
+// columna; los demás solo de fila.
+fn reuse_tail<'a>(tail: &[Token], synced: &Token, old_synced: &Token, delta: isize) -> Vec<Token<'a>> {
+    let row_delta = synced.row as isize - old_synced.row as isize;
+    let col_delta = synced.col as isize - old_synced.col as isize;
+    tail.iter()
+        .map(|token| {
+            let mut token = token.clone().into_owned();
+            if token.row == old_synced.row {
+                token.col = (token.col as isize + col_delta) as usize;
+            }
+            token.row = (token.row as isize + row_delta) as usize;
+            token.span.start = shift(token.span.start, delta) as usize;
+            token.span.end = shift(token.span.end, delta) as usize;
+            for trivia in token.leading_trivia_mut() {
+                trivia.span.start = shift(trivia.span.start, delta) as usize;
+                trivia.span.end = shift(trivia.span.end, delta) as usize;
+            }
+            token
+        })
+        .collect()
+}
@@ -0,0 +1,210 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Tablas de palabras reservadas. El lexer consulta una `KeywordTable` para
+// decidir si un identificador es una palabra reservada, de modo que cada
+// dialecto (inglés o español) produce los mismos `TokenType`.
+
+use std::str::FromStr;
+
+use crate::token::{Token, TokenType};
+
+const ENGLISH: &[(&str, TokenType)] = &[
+    ("array", TokenType::Array),
+    ("begin", TokenType::Begin),
+    ("const", TokenType::Const),
+    ("do", TokenType::Do),
+    ("else", TokenType::Else),
+    ("end", TokenType::End),
+    ("func", TokenType::Func),
+    ("if", TokenType::If),
+    ("in", TokenType::In),
+    ("let", TokenType::Let),
+    ("of", TokenType::Of),
+    ("proc", TokenType::Proc),
+    ("record", TokenType::Record),
+    ("then", TokenType::Then),
+    ("type", TokenType::Type),
+    ("var", TokenType::Var),
+    ("while", TokenType::While),
+];
+
+const SPANISH: &[(&str, TokenType)] = &[
+    ("arreglo", TokenType::Array),
+    ("inicio", TokenType::Begin),
+    ("const", TokenType::Const),
+    ("haga", TokenType::Do),
+    ("sino", TokenType::Else),
+    ("fin", TokenType::End),
+    ("func", TokenType::Func),
+    ("si", TokenType::If),
+    ("en", TokenType::In),
+    ("sea", TokenType::Let),
+    ("de", TokenType::Of),
+    ("proc", TokenType::Proc),
+    ("registro", TokenType::Record),
+    ("entonces", TokenType::Then),
+    ("tipo", TokenType::Type),
+    ("var", TokenType::Var),
+    ("mientras", TokenType::While),
+];
+
+#[derive(Debug, Clone)]
+pub struct KeywordTable {
+    // Ordenada por escritura para buscar con búsqueda binaria
+    keywords: Vec<(String, TokenType)>,
+    // La tabla en inglés sin cambios se consulta con un `match` sobre la
+    // escritura, que es bastante más rápido que la búsqueda binaria
+    english: bool,
+}
+
+impl KeywordTable {
+    pub fn empty() -> Self {
+        KeywordTable { keywords: Vec::new(), english: false }
+    }
+
+    pub fn english() -> Self {
+        KeywordTable { english: true, ..KeywordTable::from_pairs(ENGLISH) }
+    }
+
+    pub fn spanish() -> Self {
+        KeywordTable::from_pairs(SPANISH)
+    }
+
+    fn from_pairs(pairs: &[(&str, TokenType)]) -> Self {
+        let mut table = KeywordTable::empty();
+        for (spelling, token_type) in pairs {
+            table.insert(*spelling, token_type.clone());
+        }
+        table
+    }
+
+    // Agrega una palabra reservada (o reemplaza el tipo de una existente)
+    pub fn insert(&mut self, spelling: impl Into<String>, token_type: TokenType) {
+        let spelling = spelling.into();
+        self.english = false;
+        match self.keywords.binary_search_by(|(word, _)| word.as_str().cmp(&spelling)) {
+            Ok(index) => self.keywords[index].1 = token_type,
+            Err(index) => self.keywords.insert(index, (spelling, token_type)),
+        }
+    }
+
+    #[inline(always)]
+    pub fn lookup(&self, word: &str) -> Option<TokenType> {
+        if self.english {
+            return english_keyword(word);
+        }
+        self.keywords
+            .binary_search_by(|(spelling, _)| spelling.as_str().cmp(word))
+            .ok()
+            .map(|index| self.keywords[index].1.clone())
+    }
+
+    // Pares (escritura, tipo) ordenados por escritura
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &TokenType)> {
+        self.keywords
+            .iter()
+            .map(|(spelling, token_type)| (spelling.as_str(), token_type))
+    }
+
+    // Escritura de una palabra reservada en este dialecto
+    pub fn spelling(&self, token_type: &TokenType) -> Option<&str> {
+        self.keywords
+            .iter()
+            .find(|(_, keyword)| keyword == token_type)
+            .map(|(spelling, _)| spelling.as_str())
+    }
+}
+
+// Dos tablas son iguales si tienen las mismas palabras, sin importar cómo se consultan
+impl PartialEq for KeywordTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.keywords == other.keywords
+    }
+}
+
+impl Eq for KeywordTable {}
+
+impl Default for KeywordTable {
+    fn default() -> Self {
+        KeywordTable::english()
+    }
+}
+
+// Las mismas palabras que `ENGLISH`
+fn english_keyword(word: &str) -> Option<TokenType> {
+    let token_type = match word {
+        "array" => TokenType::Array,
+        "begin" => TokenType::Begin,
+        "const" => TokenType::Const,
+        "do" => TokenType::Do,
+        "else" => TokenType::Else,
+        "end" => TokenType::End,
+        "func" => TokenType::Func,
+        "if" => TokenType::If,
+        "in" => TokenType::In,
+        "let" => TokenType::Let,
+        "of" => TokenType::Of,
+        "proc" => TokenType::Proc,
+        "record" => TokenType::Record,
+        "then" => TokenType::Then,
+        "type" => TokenType::Type,
+        "var" => TokenType::Var,
+        "while" => TokenType::While,
+        _ => return None,
+    };
+    Some(token_type)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Dialect {
+    pub fn keywords(&self) -> KeywordTable {
+        match self {
+            Dialect::English => KeywordTable::english(),
+            Dialect::Spanish => KeywordTable::spanish(),
+        }
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "en" => Ok(Dialect::English),
+            "es" => Ok(Dialect::Spanish),
+            _ => Err(format!("Unknown dialect: {} (expected en or es)", s)),
+        }
+    }
+}
+
+// Reconstruye el código fuente (tokens obtenidos en modo trivia) escribiendo
+// las palabras reservadas en el dialecto `to`. Falla si un identificador del
+// programa es una palabra reservada en el dialecto destino.
+pub fn translate(tokens: &[Token], to: &KeywordTable) -> Result<String, String> {
+    let mut source = String::new();
+    for token in tokens {
+        for trivia in token.leading_trivia() {
+            source.push_str(&trivia.text);
+        }
+        if token.token_type == TokenType::Identifier && to.lookup(&token.lexeme).is_some() {
+            return Err(format!(
+                "Identifier '{}' at row {}, col {} is a keyword in the target dialect",
+                token.lexeme, token.row, token.col
+            ));
+        }
+        match to.spelling(&token.token_type) {
+            Some(spelling) => source.push_str(spelling),
+            None => source.push_str(token.text()),
+        }
+    }
+    Ok(source)
+}
@@ -0,0 +1,675 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, BufRead};
+
+pub mod options;
+
+pub use options::LexerOptions;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+use crate::keywords::KeywordTable;
+use crate::source_map::{SourceMap, Span};
+use crate::token::{Token, TokenType, Trivia, TriviaKind};
+use crate::word_size::WordSize;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LexError {
+    UnterminatedCharLiteral { row: usize, col: usize },
+    IllegalCharacter { character: char, row: usize, col: usize },
+    IntegerTooLarge { lexeme: String, maxint: i64, row: usize, col: usize },
+    UnexpectedEofInLiteral { row: usize, col: usize },
+    EmptyCharLiteral { row: usize, col: usize },
+    MultiCharLiteral { row: usize, col: usize },
+    InvalidEscape { sequence: char, row: usize, col: usize },
+    UnterminatedComment { row: usize, col: usize },
+}
+
+impl LexError {
+    pub fn position(&self) -> (usize, usize) {
+        match self {
+            LexError::UnterminatedCharLiteral { row, col }
+            | LexError::IllegalCharacter { row, col, .. }
+            | LexError::IntegerTooLarge { row, col, .. }
+            | LexError::UnexpectedEofInLiteral { row, col }
+            | LexError::EmptyCharLiteral { row, col }
+            | LexError::MultiCharLiteral { row, col }
+            | LexError::InvalidEscape { row, col, .. }
+            | LexError::UnterminatedComment { row, col } => (*row, *col),
+        }
+    }
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (row, col) = self.position();
+        write!(f, "Error at row {}, col {}: ", row, col)?;
+        match self {
+            LexError::UnterminatedCharLiteral { .. } => write!(f, "unterminated character literal"),
+            LexError::IllegalCharacter { character, .. } => {
+                write!(f, "illegal character {:?}", character)
+            }
+            LexError::IntegerTooLarge { lexeme, maxint, .. } => {
+                write!(f, "integer literal {} exceeds maxint ({})", lexeme, maxint)
+            }
+            LexError::UnexpectedEofInLiteral { .. } => {
+                write!(f, "unexpected end of file in character literal")
+            }
+            LexError::EmptyCharLiteral { .. } => write!(f, "empty character literal"),
+            LexError::MultiCharLiteral { .. } => {
+                write!(f, "character literal contains more than one character")
+            }
+            LexError::InvalidEscape { sequence, .. } => {
+                write!(f, "invalid escape sequence '\\{}'", sequence)
+            }
+            LexError::UnterminatedComment { .. } => write!(f, "unterminated block comment"),
+        }
+    }
+}
+
+impl std::error::Error for LexError {}
+
+pub struct Lexer<'a> {
+    source_map: SourceMap<'a>,
+    // Si el código fuente viene de un `BufRead`, se lee línea por línea según se
+    // necesite y cada línea se agrega al `SourceMap`, que la conserva
+    reader: Option<Box<dyn BufRead + 'a>>,
+    line_buffer: String,
+    io_error: Option<io::Error>,
+    // Offsets en bytes del carácter actual y del siguiente
+    curr_pos: usize,
+    next_pos: usize,
+    curr_char: char,
+    errors: Vec<LexError>,
+    finished: bool,
+    keep_trivia: bool,
+    block_comments: bool,
+    keywords: KeywordTable,
+    normalize_identifiers: bool,
+    ignore_case: bool,
+    word_size: WordSize,
+    // (offset, línea, columna) de la última posición calculada
+    last_position: Cell<(usize, usize, usize)>,
+}
+
+impl<'a> Lexer<'a> {
+    // Con un `&str` los lexemas se toman prestados del código fuente (sin copias);
+    // con un `String` cada token lleva una copia propia de su lexema, además del
+    // código fuente completo que guarda el lexer.
+    pub fn new(input: impl Into<Cow<'a, str>>) -> Lexer<'a> {
+        Lexer::with_source(SourceMap::new(input), None)
+    }
+
+    // Lee el `BufRead` por líneas a medida que se piden tokens, pero no es un lexer
+    // en streaming: todo lo leído queda en el `SourceMap` (lo necesitan `seek` y
+    // el cálculo de línea/columna), así que la memoria crece con el tamaño de la
+    // entrada, y cada lexema se copia como con un `String`.
+    pub fn from_reader(reader: impl BufRead + 'a) -> Lexer<'a> {
+        Lexer::with_source(SourceMap::new(String::new()), Some(Box::new(reader)))
+    }
+
+    fn with_source(source_map: SourceMap<'a>, reader: Option<Box<dyn BufRead + 'a>>) -> Lexer<'a> {
+        let mut lexer = Lexer {
+            source_map,
+            reader,
+            line_buffer: String::new(),
+            io_error: None,
+            curr_pos: 0,
+            next_pos: 0,
+            curr_char: '\0',
+            errors: Vec::new(),
+            finished: false,
+            keep_trivia: false,
+            block_comments: false,
+            keywords: KeywordTable::default(),
+            normalize_identifiers: false,
+            ignore_case: false,
+            word_size: WordSize::default(),
+            last_position: Cell::new((0, 1, 1)),
+        };
+        lexer.read_char();
+        lexer
+    }
+
+    // Modo trivia: cada token lleva los comentarios y espacios que lo preceden
+    // (el EOF lleva los del final del archivo), de modo que `token::to_source`
+    // reconstruye el código fuente exacto
+    pub fn with_trivia(mut self) -> Self {
+        self.keep_trivia = true;
+        self
+    }
+
+    // Palabras reservadas del dialecto (por defecto, las de Triangle en inglés)
+    pub fn with_keywords(mut self, keywords: KeywordTable) -> Self {
+        self.keywords = keywords;
+        self
+    }
+
+    // Los identificadores se normalizan a NFC, de modo que una misma letra
+    // escrita compuesta o descompuesta (`año`) da el mismo nombre
+    pub fn with_normalized_identifiers(mut self) -> Self {
+        self.normalize_identifiers = true;
+        self
+    }
+
+    // Palabras reservadas e identificadores sin distinguir mayúsculas (`Begin` es `begin`)
+    pub fn with_ignore_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    // Extensión del dialecto: comentarios de bloque `{! ... !}`, que pueden anidarse
+    pub fn with_block_comments(mut self) -> Self {
+        self.block_comments = true;
+        self
+    }
+
+    // Los literales enteros mayores que `word_size.maxint()` son errores léxicos
+    pub fn with_word_size(mut self, word_size: WordSize) -> Self {
+        self.word_size = word_size;
+        self
+    }
+
+    pub fn word_size(&self) -> WordSize {
+        self.word_size
+    }
+
+    // Continúa el análisis desde `offset`, que debe ser un límite entre tokens
+    // (lo usa el re-análisis incremental)
+    pub(crate) fn seek(&mut self, offset: usize) {
+        let (line, col) = self.source_map.line_col(offset);
+        self.last_position.set((offset, line, col));
+        self.next_pos = offset;
+        self.finished = false;
+        self.read_char();
+    }
+
+    // Errores léxicos encontrados hasta el momento
+    pub fn errors(&self) -> &[LexError] {
+        &self.errors
+    }
+
+    // Error de lectura del `BufRead`, si lo hubo (el lexer lo trata como fin de archivo)
+    pub fn take_io_error(&mut self) -> Option<io::Error> {
+        self.io_error.take()
+    }
+
+    pub fn source_map(&self) -> &SourceMap<'a> {
+        &self.source_map
+    }
+
+    fn input(&self) -> &str {
+        self.source_map.source()
+    }
+
+    fn at_eof(&self) -> bool {
+        self.curr_pos >= self.input().len()
+    }
+
+    // Lee líneas del `BufRead` hasta que el offset `pos` esté disponible
+    fn fill(&mut self, pos: usize) {
+        while pos >= self.input().len() {
+            let reader = match self.reader.as_mut() {
+                Some(reader) => reader,
+                None => return,
+            };
+            self.line_buffer.clear();
+            match reader.read_line(&mut self.line_buffer) {
+                Ok(0) => self.reader = None,
+                Ok(_) => self.source_map.push_str(&self.line_buffer),
+                Err(err) => {
+                    self.io_error = Some(err);
+                    self.reader = None;
+                }
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn read_char(&mut self) {
+        if self.reader.is_some() {
+            self.fill(self.next_pos);
+        }
+        match char_at(self.input(), self.next_pos) {
+            Some(ch) => {
+                self.curr_char = ch;
+                self.curr_pos = self.next_pos;
+                self.next_pos += ch.len_utf8();
+            }
+            None => {
+                self.curr_char = '\0';
+                self.curr_pos = self.input().len();
+            }
+        }
+        // El carácter siguiente también debe estar disponible para `look_ahead`
+        if self.reader.is_some() {
+            self.fill(self.next_pos);
+        }
+    }
+
+    fn look_ahead(&self) -> char {
+        char_at(self.input(), self.next_pos).unwrap_or('\0')
+    }
+
+    // Los tokens salen en orden, así que la posición se calcula avanzando desde
+    // la última posición consultada (usando el índice de líneas del `SourceMap`)
+    fn line_col(&self, offset: usize) -> (usize, usize) {
+        let (last_offset, mut line, mut col) = self.last_position.get();
+        if offset < last_offset {
+            return self.source_map.line_col(offset);
+        }
+
+        let mut from = last_offset;
+        while line < self.source_map.line_count() && self.source_map.line_start(line + 1) <= offset {
+            line += 1;
+            from = self.source_map.line_start(line);
+            col = 1;
+        }
+        // Cuenta caracteres, no bytes: los bytes de continuación de UTF-8 no suman
+        col += self.input().as_bytes()[from..offset]
+            .iter()
+            .filter(|&&byte| byte & 0xC0 != 0x80)
+            .count();
+
+        self.last_position.set((offset, line, col));
+        (line, col)
+    }
+
+    // Agrega a `trivia` los comentarios y espacios saltados, si se pidió conservarlos
+    #[inline(always)]
+    fn skip_whitespace_and_comments(&mut self, mut trivia: Option<&mut Vec<Trivia<'a>>>) {
+        loop {
+            let start_pos = self.curr_pos;
+            let kind = if self.curr_char.is_whitespace() {
+                while self.curr_char.is_whitespace() {
+                    self.read_char();
+                }
+                TriviaKind::Whitespace
+            } else if self.curr_char == '!' {
+                while self.curr_char != '\n' && !self.at_eof() {
+                    self.read_char();
+                }
+                TriviaKind::Comment
+            } else if self.at_block_comment() {
+                self.skip_block_comment();
+                TriviaKind::Comment
+            } else {
+                return;
+            };
+            if let Some(trivia) = trivia.as_deref_mut() {
+                let span = Span::new(start_pos, self.curr_pos);
+                trivia.push(Trivia { kind, text: self.source_map.slice(span), span });
+            }
+        }
+    }
+
+    #[inline]
+    fn at_block_comment(&self) -> bool {
+        self.block_comments && self.curr_char == '{' && self.look_ahead() == '!'
+    }
+
+    // Salta un comentario `{! ... !}` con sus comentarios anidados; si no se
+    // cierra, el error se reporta en la posición donde se abrió
+    fn skip_block_comment(&mut self) {
+        let (row, col) = self.line_col(self.curr_pos);
+        let mut depth = 0;
+        loop {
+            if self.at_eof() {
+                self.errors.push(LexError::UnterminatedComment { row, col });
+                return;
+            }
+            if self.curr_char == '{' && self.look_ahead() == '!' {
+                depth += 1;
+                self.read_char();
+            } else if self.curr_char == '!' && self.look_ahead() == '}' {
+                depth -= 1;
+                self.read_char();
+                if depth == 0 {
+                    self.read_char();
+                    return;
+                }
+            }
+            self.read_char();
+        }
+    }
+
+    fn read_identifier(&mut self) -> Token<'a> {
+        let start_pos = self.curr_pos;
+
+        while is_identifier_char(self.curr_char) {
+            self.read_char();
+        }
+
+        let lexeme = self.slice(start_pos);
+        if self.normalize_identifiers || self.ignore_case {
+            return self.canonical_identifier(lexeme, start_pos);
+        }
+        let token_type = self.keywords.lookup(&lexeme).unwrap_or(TokenType::Identifier);
+        self.make_token(token_type, lexeme, start_pos)
+    }
+
+    // Con normalización o sin distinguir mayúsculas, el lexema es el nombre
+    // canónico y la escritura original queda en `source_text`
+    fn canonical_identifier(&self, lexeme: Cow<'a, str>, start_pos: usize) -> Token<'a> {
+        let canonical = self.canonical_name(&lexeme);
+        let token_type = self
+            .keywords
+            .lookup(canonical.as_deref().unwrap_or(&lexeme))
+            .unwrap_or(TokenType::Identifier);
+
+        match canonical {
+            Some(name) => {
+                self.make_token(token_type, name, start_pos).with_source_text(lexeme)
+            }
+            None => self.make_token(token_type, lexeme, start_pos),
+        }
+    }
+
+    // Nombre canónico de un identificador, o `None` si coincide con su escritura
+    fn canonical_name(&self, lexeme: &str) -> Option<String> {
+        let mut name = None;
+        if self.normalize_identifiers && !is_nfc(lexeme) {
+            name = Some(lexeme.nfc().collect::<String>());
+        }
+        if self.ignore_case {
+            let current = name.as_deref().unwrap_or(lexeme);
+            if current.chars().any(char::is_uppercase) {
+                name = Some(current.to_lowercase());
+            }
+        }
+        name
+    }
+
+    fn read_number(&mut self) -> Token<'a> {
+        let start_pos = self.curr_pos;
+
+        while is_digit(self.curr_char) {
+            self.read_char();
+        }
+
+        let lexeme = self.slice(start_pos);
+        if self.word_size.parse_literal(&lexeme).is_none() {
+            let (row, col) = self.line_col(start_pos);
+            self.errors.push(LexError::IntegerTooLarge {
+                lexeme: lexeme.to_string(),
+                maxint: self.word_size.maxint(),
+                row,
+                col,
+            });
+        }
+        // El token se conserva como `IntegerLiteral` para que el parser también lo reporte
+        self.make_token(TokenType::IntegerLiteral, lexeme, start_pos)
+    }
+
+    fn read_operator(&mut self) -> Token<'a> {
+        let start_pos = self.curr_pos;
+
+        while is_operator_char(self.curr_char) {
+            self.read_char();
+        }
+
+        // Todos los operadores (incluso los declarados por el usuario) son `Operator`;
+        // su clase se obtiene con `Token::operator_kind`
+        let lexeme = self.slice(start_pos);
+        self.make_token(TokenType::Operator, lexeme, start_pos)
+    }
+
+    // Literal de carácter: 'c', comilla duplicada ('''' o ''') o escape ('\n', '\t',
+    // '\r', '\0', '\'', '\\'). '\' también es la barra invertida cuando a `\'` no le
+    // sigue otra comilla. El lexema del token es el carácter ya decodificado.
+    fn read_character(&mut self) -> Token<'a> {
+        let start_pos = self.curr_pos;
+        let (row, col) = self.line_col(start_pos);
+
+        self.read_char(); // Skip opening '
+        if self.at_eof() {
+            self.errors.push(LexError::UnexpectedEofInLiteral { row, col });
+            return self.illegal_token(start_pos);
+        }
+
+        // Un carácter sin escape se toma prestado del código fuente
+        let mut plain_span = None;
+        let char_lit = match self.curr_char {
+            '\n' => {
+                self.errors.push(LexError::UnterminatedCharLiteral { row, col });
+                return self.illegal_token(start_pos);
+            }
+            '\'' => {
+                if self.look_ahead() != '\'' {
+                    self.read_char(); // Skip closing '
+                    self.errors.push(LexError::EmptyCharLiteral { row, col });
+                    return self.illegal_token(start_pos);
+                }
+                self.read_char();
+                if self.look_ahead() == '\'' {
+                    self.read_char(); // Comilla duplicada
+                }
+                '\''
+            }
+            '\\' => {
+                let (escape_row, escape_col) = self.line_col(self.curr_pos);
+                self.read_char();
+                if self.curr_char == '\'' && self.look_ahead() != '\'' {
+                    // '\': esta comilla es la de cierre
+                    '\\'
+                } else {
+                    let decoded = match self.curr_char {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '0' => '\0',
+                        '\'' => '\'',
+                        '\\' => '\\',
+                        _ if self.at_eof() => {
+                            self.errors.push(LexError::UnexpectedEofInLiteral { row, col });
+                            return self.illegal_token(start_pos);
+                        }
+                        '\n' => {
+                            self.errors.push(LexError::UnterminatedCharLiteral { row, col });
+                            return self.illegal_token(start_pos);
+                        }
+                        sequence => {
+                            self.errors.push(LexError::InvalidEscape {
+                                sequence,
+                                row: escape_row,
+                                col: escape_col,
+                            });
+                            sequence
+                        }
+                    };
+                    self.read_char();
+                    decoded
+                }
+            }
+            ch => {
+                plain_span = Some(Span::new(self.curr_pos, self.next_pos));
+                self.read_char();
+                ch
+            }
+        };
+
+        if self.at_eof() {
+            self.errors.push(LexError::UnexpectedEofInLiteral { row, col });
+            return self.illegal_token(start_pos);
+        }
+        if self.curr_char != '\'' {
+            if self.closing_quote_on_line() {
+                // 'abc': se consume hasta la comilla de cierre
+                while self.curr_char != '\'' {
+                    self.read_char();
+                }
+                self.read_char();
+                self.errors.push(LexError::MultiCharLiteral { row, col });
+            } else {
+                // No se consume el carácter siguiente: puede iniciar el próximo token
+                self.errors.push(LexError::UnterminatedCharLiteral { row, col });
+            }
+            return self.illegal_token(start_pos);
+        }
+        self.read_char(); // Skip closing '
+
+        let lexeme = match plain_span {
+            Some(span) => self.source_map.slice(span),
+            None => Cow::Owned(char_lit.to_string()),
+        };
+        self.make_token(TokenType::CharLiteral, lexeme, start_pos)
+    }
+
+    fn closing_quote_on_line(&self) -> bool {
+        let rest = &self.input()[self.curr_pos..];
+        rest.split('\n').next().unwrap_or("").contains('\'')
+    }
+
+    pub fn next_token(&mut self) -> Token<'a> {
+        if self.keep_trivia {
+            return self.next_token_with_trivia();
+        }
+        self.skip_whitespace_and_comments(None);
+        self.scan_token()
+    }
+
+    fn next_token_with_trivia(&mut self) -> Token<'a> {
+        let mut trivia = Vec::new();
+        self.skip_whitespace_and_comments(Some(&mut trivia));
+        let mut token = self.scan_token();
+        token.set_leading_trivia(trivia);
+        let text = self.source_map.slice(token.span);
+        if text != token.lexeme {
+            token = token.with_source_text(text);
+        }
+        token
+    }
+
+    fn scan_token(&mut self) -> Token<'a> {
+        let start_pos = self.curr_pos;
+
+        let tok = match self.curr_char {
+            '{' => self.create_token(TokenType::LeftBrace),
+            '}' => self.create_token(TokenType::RightBrace),
+            '(' => self.create_token(TokenType::LeftParen),
+            ')' => self.create_token(TokenType::RightParen),
+            '[' => self.create_token(TokenType::LeftBracket),
+            ']' => self.create_token(TokenType::RightBracket),
+            ':' => {
+                if self.look_ahead() == '=' {
+                    self.read_char();
+                    let span = Span::new(start_pos, self.next_pos);
+                    Token::new(TokenType::Assign, self.source_map.slice(span), 0, 0).with_span(span)
+                } else {
+                    self.create_token(TokenType::Colon)
+                }
+            }
+            ';' => self.create_token(TokenType::Semicolon),
+            ',' => self.create_token(TokenType::Comma),
+            '.' => self.create_token(TokenType::Period),
+            '=' => self.create_token(TokenType::Equals),
+            '~' => self.create_token(TokenType::Tilde),
+            '\'' => {
+                return self.read_character();
+            }
+            '\0' if self.at_eof() => {
+                self.finished = true;
+                return self.make_token(TokenType::EOF, "", start_pos);
+            }
+            _ => {
+                if is_letter(self.curr_char) {
+                    return self.read_identifier();
+                } else if is_digit(self.curr_char) {
+                    return self.read_number();
+                } else if is_operator_char(self.curr_char) {
+                    return self.read_operator();
+                } else {
+                    let (row, col) = self.line_col(start_pos);
+                    self.errors.push(LexError::IllegalCharacter {
+                        character: self.curr_char,
+                        row,
+                        col,
+                    });
+                    self.create_token(TokenType::Illegal)
+                }
+            }
+        };
+
+        self.read_char();
+        self.locate(tok)
+    }
+
+    // Token de un solo carácter (el actual); todavía no se ha avanzado
+    fn create_token(&self, token_type: TokenType) -> Token<'a> {
+        let span = Span::new(self.curr_pos, self.next_pos);
+        Token::new(token_type, self.source_map.slice(span), 0, 0).with_span(span)
+    }
+
+    // Token que empieza en `start_pos` y termina en la posición actual
+    fn make_token(&self, token_type: TokenType, lexeme: impl Into<Cow<'a, str>>, start_pos: usize) -> Token<'a> {
+        let token = Token::new(token_type, lexeme, 0, 0).with_span(Span::new(start_pos, self.curr_pos));
+        self.locate(token)
+    }
+
+    fn illegal_token(&self, start_pos: usize) -> Token<'a> {
+        self.make_token(TokenType::Illegal, self.slice(start_pos), start_pos)
+    }
+
+    // Lexema desde `start_pos` hasta la posición actual
+    fn slice(&self, start_pos: usize) -> Cow<'a, str> {
+        self.source_map.slice(Span::new(start_pos, self.curr_pos))
+    }
+
+    // Fila y columna se calculan a partir del span, no de contadores incrementales
+    fn locate(&self, mut token: Token<'a>) -> Token<'a> {
+        let (row, col) = self.line_col(token.span.start);
+        token.row = row;
+        token.col = col;
+        token
+    }
+}
+
+// Produce los tokens hasta el EOF inclusive
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        if self.finished {
+            None
+        } else {
+            Some(self.next_token())
+        }
+    }
+}
+
+#[inline(always)]
+fn char_at(input: &str, pos: usize) -> Option<char> {
+    match input.as_bytes().get(pos) {
+        Some(&byte) if byte.is_ascii() => Some(byte as char),
+        Some(_) => input[pos..].chars().next(),
+        None => None,
+    }
+}
+
+fn is_letter(ch: char) -> bool {
+    ch.is_alphabetic()
+}
+
+// Caracteres que continúan un identificador: letras, dígitos y marcas
+// combinantes (como la tilde de una `ñ` descompuesta)
+pub(crate) fn is_identifier_char(ch: char) -> bool {
+    is_letter(ch) || is_digit(ch) || is_mark(ch)
+}
+
+fn is_mark(ch: char) -> bool {
+    !ch.is_ascii() && is_combining_mark(ch)
+}
+
+fn is_digit(ch: char) -> bool {
+    ch.is_ascii_digit()
+}
+
+pub(crate) fn is_operator_char(ch: char) -> bool {
+    matches!(ch, '+' | '-' | '*' | '/' | '=' | '<' | '>' | '&' | '@' | '%' | '^' | '?' | '\\')
+}
@@ -0,0 +1,78 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Opciones del lexer que se pueden elegir desde la línea de comandos. Los
+// binarios (`tokenize`, `tokens`, `parse`, `translate`) las reconocen con
+// `parse_arg` y construyen el lexer con `lexer` o `configure`, de modo que
+// todos aceptan las mismas.
+
+use std::borrow::Cow;
+
+use super::Lexer;
+use crate::keywords::Dialect;
+use crate::word_size::WordSize;
+
+pub const USAGE: &str = "[--word-size 16|64] [--block-comments] [--dialect en|es] [--nfc] [--ignore-case]";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LexerOptions {
+    pub word_size: WordSize,
+    pub block_comments: bool,
+    pub dialect: Dialect,
+    pub normalize_identifiers: bool,
+    pub ignore_case: bool,
+}
+
+impl LexerOptions {
+    // Reconoce una opción del lexer al inicio de `args` y devuelve cuántos
+    // argumentos consumió (0 si `args[0]` no es una opción del lexer)
+    pub fn parse_arg(&mut self, args: &[String]) -> Result<usize, String> {
+        let value = || args.get(1).ok_or_else(|| format!("Missing value for {}", args[0]));
+        match args.first().map(String::as_str) {
+            Some("--word-size") => {
+                self.word_size = value()?.parse()?;
+                Ok(2)
+            }
+            Some("--dialect") => {
+                self.dialect = value()?.parse()?;
+                Ok(2)
+            }
+            Some("--block-comments") => {
+                self.block_comments = true;
+                Ok(1)
+            }
+            Some("--nfc") => {
+                self.normalize_identifiers = true;
+                Ok(1)
+            }
+            Some("--ignore-case") => {
+                self.ignore_case = true;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+
+    pub fn lexer<'a>(&self, source: impl Into<Cow<'a, str>>) -> Lexer<'a> {
+        self.configure(Lexer::new(source))
+    }
+
+    // Aplica las opciones a un lexer ya creado (por ejemplo, con `from_reader`)
+    pub fn configure<'a>(&self, lexer: Lexer<'a>) -> Lexer<'a> {
+        let mut lexer = lexer
+            .with_word_size(self.word_size)
+            .with_keywords(self.dialect.keywords());
+        if self.block_comments {
+            lexer = lexer.with_block_comments();
+        }
+        if self.normalize_identifiers {
+            lexer = lexer.with_normalized_identifiers();
+        }
+        if self.ignore_case {
+            lexer = lexer.with_ignore_case();
+        }
+        lexer
+    }
+}
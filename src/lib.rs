@@ -0,0 +1,17 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+pub mod ast;
+pub mod incremental;
+pub mod keywords;
+pub mod lexer;
+pub mod operators;
+pub mod parser;
+pub mod regex_lexer;
+pub mod resolver;
+pub mod source_map;
+pub mod token;
+pub mod token_diff;
+pub mod token_file;
+pub mod word_size;
@@ -0,0 +1,110 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Tabla de precedencia de los operadores binarios. El parser la usa para
+// agrupar las expresiones por precedencia (precedence climbing): un número
+// mayor liga más fuerte. Los operadores que no están en la tabla (por ejemplo,
+// los declarados por el usuario) usan `default_precedence`.
+
+use std::collections::HashMap;
+
+// Nivel más alto permitido: el parser sube un nivel para el operando derecho de
+// los operadores que asocian a la izquierda, así que `u8::MAX` no se puede usar
+pub const MAX_LEVEL: u8 = u8::MAX - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Precedence {
+    pub level: u8,
+    pub associativity: Associativity,
+}
+
+#[derive(Debug, Clone)]
+pub struct OperatorTable {
+    operators: HashMap<String, Precedence>,
+    default_precedence: Precedence,
+}
+
+impl OperatorTable {
+    // Sin niveles: todos los operadores tienen la misma precedencia y asocian
+    // a la izquierda, como en la gramática original de Triangle
+    pub fn flat() -> Self {
+        OperatorTable {
+            operators: HashMap::new(),
+            default_precedence: Precedence { level: 1, associativity: Associativity::Left },
+        }
+    }
+
+    // Niveles del ambiente estándar de Triangle, de menor a mayor:
+    // `\/`, `/\`, comparaciones, `+ -` y `* / //`
+    pub fn standard() -> Self {
+        let mut table = OperatorTable::flat();
+        let levels: &[&[&str]] = &[
+            &["\\/"],
+            &["/\\"],
+            &["<", "<=", ">", ">=", "=", "\\="],
+            &["+", "-"],
+            &["*", "/", "//"],
+        ];
+        for (level, operators) in levels.iter().enumerate() {
+            for operator in *operators {
+                let precedence = Precedence { level: level as u8 + 1, associativity: Associativity::Left };
+                table.operators.insert(operator.to_string(), precedence);
+            }
+        }
+        table.default_precedence = Precedence {
+            level: levels.len() as u8 + 1,
+            associativity: Associativity::Left,
+        };
+        table
+    }
+
+    // Falla si `level` es mayor que `MAX_LEVEL`
+    pub fn insert(
+        &mut self,
+        operator: impl Into<String>,
+        level: u8,
+        associativity: Associativity,
+    ) -> Result<(), String> {
+        check_level(level)?;
+        self.operators
+            .insert(operator.into(), Precedence { level, associativity });
+        Ok(())
+    }
+
+    pub fn set_default_precedence(&mut self, level: u8, associativity: Associativity) -> Result<(), String> {
+        check_level(level)?;
+        self.default_precedence = Precedence { level, associativity };
+        Ok(())
+    }
+
+    pub fn precedence(&self, operator: &str) -> Precedence {
+        self.operators
+            .get(operator)
+            .copied()
+            .unwrap_or(self.default_precedence)
+    }
+}
+
+fn check_level(level: u8) -> Result<(), String> {
+    if level > MAX_LEVEL {
+        return Err(format!(
+            "Operator precedence level {} is above the maximum ({})",
+            level, MAX_LEVEL
+        ));
+    }
+    Ok(())
+}
+
+impl Default for OperatorTable {
+    fn default() -> Self {
+        OperatorTable::standard()
+    }
+}
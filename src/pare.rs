@@ -8,8 +8,7 @@ use std::path::Path;
 use std::env;
 
 fn remove_parentheses(input: &str) -> String {
-    let new = input.replace("(", "").replace(")", "").replace(",", "").replace("[", "").replace("]", "");
-    new
+    input.replace("(", "").replace(")", "").replace(",", "").replace("[", "").replace("]", "")
 }
 
 fn main() -> io::Result<()> {
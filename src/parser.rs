@@ -0,0 +1,615 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+use crate::ast::{ASTNode, TypeDenoter, Vname};
+use crate::lexer::{LexError, Lexer};
+use crate::operators::{Associativity, OperatorTable};
+use crate::token::{Token, TokenType};
+use crate::word_size::WordSize;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SyntaxError {
+    UnexpectedToken {
+        expected: TokenType,
+        found: TokenType,
+        row: usize,
+        col: usize,
+    },
+    IntegerOutOfRange {
+        lexeme: String,
+        maxint: i64,
+        row: usize,
+        col: usize,
+    },
+    // El lexema de un literal de carácter debe ser exactamente un carácter
+    InvalidCharLiteral {
+        lexeme: String,
+        row: usize,
+        col: usize,
+    },
+}
+
+// Origen de los tokens: el lexer (que además acumula los errores léxicos) o
+// una secuencia de tokens ya leída
+enum TokenSource<'a> {
+    Lexer(Box<Lexer<'a>>),
+    Tokens(Box<dyn Iterator<Item = Token<'a>> + 'a>),
+}
+
+impl<'a> Iterator for TokenSource<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        match self {
+            TokenSource::Lexer(lexer) => lexer.next(),
+            TokenSource::Tokens(tokens) => tokens.next(),
+        }
+    }
+}
+
+pub struct Parser<'a> {
+  pub current_token: Token<'a>,
+  tokens: TokenSource<'a>,
+  word_size: WordSize,
+  operators: OperatorTable,
+}
+impl<'a> Parser<'a> {
+  pub fn new(tokens: Vec<Token<'a>>) -> Self {
+      Parser::from_tokens(TokenSource::Tokens(Box::new(tokens.into_iter())))
+  }
+
+  // Los tokens se piden al lexer conforme el parser avanza, sin archivo intermedio
+  pub fn from_lexer(lexer: Lexer<'a>) -> Self {
+      let word_size = lexer.word_size();
+      Parser::from_tokens(TokenSource::Lexer(Box::new(lexer))).with_word_size(word_size)
+  }
+
+  // Errores léxicos de los tokens leídos hasta el momento (solo con `from_lexer`;
+  // un archivo de tokens no los conserva)
+  pub fn lex_errors(&self) -> &[LexError] {
+      match &self.tokens {
+          TokenSource::Lexer(lexer) => lexer.errors(),
+          TokenSource::Tokens(_) => &[],
+      }
+  }
+
+  // Con `from_lexer` se usa el tamaño de palabra del lexer
+  pub fn with_word_size(mut self, word_size: WordSize) -> Self {
+      self.word_size = word_size;
+      self
+  }
+
+  pub fn word_size(&self) -> WordSize {
+      self.word_size
+  }
+
+  // Precedencia de los operadores binarios (por defecto, la del ambiente estándar)
+  pub fn with_operators(mut self, operators: OperatorTable) -> Self {
+      self.operators = operators;
+      self
+  }
+
+  fn from_tokens(mut tokens: TokenSource<'a>) -> Self {
+      let current_token = tokens
+          .next()
+          .unwrap_or_else(|| Token::new(TokenType::EOF, "", 1, 1));
+      Parser {
+          current_token,
+          tokens,
+          word_size: WordSize::default(),
+          operators: OperatorTable::default(),
+      }
+  }
+
+  // Al llegar al final se queda en el último token (EOF)
+  fn next_token(&mut self) {
+    if let Some(token) = self.tokens.next() {
+        self.current_token = token;
+    }
+  }
+
+  fn accept(&mut self, expected: TokenType) -> Result<(), SyntaxError> {
+      if self.current_token.token_type == expected {
+          self.next_token();
+          Ok(())
+      } else {
+          Err(SyntaxError::UnexpectedToken {
+              expected,
+              found: self.current_token.token_type.clone(),
+              row: self.current_token.row,
+              col: self.current_token.col,
+          })
+      }
+  }
+
+  // El programa es un comando seguido del final del archivo: cualquier token
+  // sobrante (`x := 1 y := 2`, un `end` de más) es un error
+  pub fn parse(&mut self) -> Result<ASTNode, SyntaxError> {
+      let command = self.parse_command()?;
+      self.accept(TokenType::EOF)?;
+      Ok(command)
+  }
+
+  fn parse_command(&mut self) -> Result<ASTNode, SyntaxError> {
+      let command = self.parse_single_command()?;
+      let mut commands = vec![command];
+
+      while self.current_token.token_type == TokenType::Semicolon {
+          self.next_token();
+          commands.push(self.parse_single_command()?);
+      }
+
+      if commands.len() == 1 {
+          Ok(commands.pop().unwrap())
+      } else {
+          Ok(ASTNode::Command(commands))
+      }
+  }
+
+  fn parse_single_command(&mut self) -> Result<ASTNode, SyntaxError> {
+      match self.current_token.token_type {
+          TokenType::Let => {
+              self.next_token();
+              let decl = self.parse_declaration_sequence()?;
+              self.accept(TokenType::In)?;
+              let cmd = self.parse_command()?;
+              Ok(ASTNode::Let(Box::new(decl), Box::new(cmd)))
+          }
+          TokenType::Const => {
+              self.next_token();
+              let name = self.parse_identifier()?;
+              self.accept(TokenType::Tilde)?;
+              let expr = self.parse_expression()?;
+              Ok(ASTNode::Const(name, Box::new(expr)))
+          }
+          TokenType::Var => {
+              self.next_token();
+              let name = self.parse_identifier()?;
+              self.accept(TokenType::Colon)?;
+              let type_denoter = self.parse_type_denoter()?;
+              Ok(ASTNode::Var(name, type_denoter))
+          }
+          TokenType::Func => {
+              self.next_token();
+              let name = self.parse_identifier()?;
+              self.accept(TokenType::LeftParen)?;
+              let params = self.parse_formal_parameter_sequence()?;
+              self.accept(TokenType::RightParen)?;
+              self.accept(TokenType::Colon)?;
+              let return_type = self.parse_type_denoter()?;
+              self.accept(TokenType::Tilde)?;
+              let body = self.parse_expression()?;
+              Ok(ASTNode::Func(name, params, return_type, Box::new(body)))
+          }
+          TokenType::If => {
+              self.next_token();
+              let condition = self.parse_expression()?;
+              self.accept(TokenType::Then)?;
+              let then_branch = self.parse_command()?;
+              self.accept(TokenType::Else)?;
+              let else_branch = self.parse_command()?;
+              Ok(ASTNode::If(
+                  Box::new(condition),
+                  TokenType::Then,
+                  Box::new(then_branch),
+                  TokenType::Else,
+                  Box::new(else_branch),
+              ))
+          }
+          TokenType::While => {
+              self.next_token();
+              let condition = self.parse_expression()?;
+              self.accept(TokenType::Do)?;
+              let body = self.parse_command()?;
+              Ok(ASTNode::While(Box::new(condition), Box::new(body)))
+          }
+          TokenType::Begin => {
+              // Soporte para comandos 'begin ... end'
+              self.next_token();
+              let commands = self.parse_command()?;
+              self.accept(TokenType::End)?;
+              Ok(commands)
+          }
+          TokenType::Identifier => {
+              let name = self.parse_identifier()?;
+              if self.current_token.token_type == TokenType::LeftParen {
+                  self.next_token();
+                  let params = self.parse_actual_parameter_sequence()?;
+                  self.accept(TokenType::RightParen)?;
+                  return Ok(ASTNode::Call(name, params));
+              }
+              let vname = self.parse_vname_rest(Vname::Simple(name))?;
+              if self.current_token.token_type == TokenType::Assign {
+                  self.next_token();
+                  let expr = self.parse_expression()?;
+                  Ok(ASTNode::Assign(vname, Box::new(expr)))
+              } else {
+                  Err(SyntaxError::UnexpectedToken {
+                      expected: TokenType::Assign,
+                      found: self.current_token.token_type.clone(),
+                      row: self.current_token.row,
+                      col: self.current_token.col,
+                  })
+              }
+          }
+          _ => Err(SyntaxError::UnexpectedToken {
+              expected: TokenType::Identifier,
+              found: self.current_token.token_type.clone(),
+              row: self.current_token.row,
+              col: self.current_token.col,
+          }),
+      }
+  }
+
+  fn parse_expression(&mut self) -> Result<ASTNode, SyntaxError> {
+      match self.current_token.token_type {
+          TokenType::Let => {
+              self.next_token();
+              let decls = self.parse_declaration_sequence()?;
+              self.accept(TokenType::In)?;
+              let expr = self.parse_expression()?;
+              Ok(ASTNode::Let(Box::new(decls), Box::new(expr)))
+          }
+          TokenType::If => {
+              self.next_token();
+              let condition = self.parse_expression()?;
+              self.accept(TokenType::Then)?;
+              let then_branch = self.parse_expression()?;
+              self.accept(TokenType::Else)?;
+              let else_branch = self.parse_expression()?;
+              Ok(ASTNode::If(
+                  Box::new(condition),
+                  TokenType::Then,
+                  Box::new(then_branch),
+                  TokenType::Else,
+                  Box::new(else_branch),
+              ))
+          }
+          _ => self.parse_second_expression(),
+      }
+  }
+
+  fn parse_declaration_sequence(&mut self) -> Result<ASTNode, SyntaxError> {
+      let mut declarations = vec![self.parse_single_declaration()?];
+
+      while self.current_token.token_type == TokenType::Semicolon {
+          self.next_token();
+          declarations.push(self.parse_single_declaration()?);
+      }
+
+      if declarations.len() == 1 {
+          Ok(declarations.pop().unwrap())
+      } else {
+          Ok(ASTNode::Declaration(declarations))
+      }
+  }
+
+  fn parse_single_declaration(&mut self) -> Result<ASTNode, SyntaxError> {
+      match self.current_token.token_type {
+          TokenType::Const => {
+              self.next_token();
+              let name = self.parse_identifier()?;
+              self.accept(TokenType::Tilde)?;
+              let expr = self.parse_expression()?;
+              Ok(ASTNode::Const(name, Box::new(expr)))
+          }
+          TokenType::Var => {
+              self.next_token();
+              let name = self.parse_identifier()?;
+              self.accept(TokenType::Colon)?;
+              let type_denoter = self.parse_type_denoter()?;
+              Ok(ASTNode::Var(name, type_denoter))
+          }
+          TokenType::Func => {
+              self.next_token();
+              if self.current_token.operator_kind().is_some() {
+                  return self.parse_operator_declaration();
+              }
+              let name = self.parse_identifier()?;
+              self.accept(TokenType::LeftParen)?;
+              let params = self.parse_formal_parameter_sequence()?;
+              self.accept(TokenType::RightParen)?;
+              self.accept(TokenType::Colon)?;
+              let return_type = self.parse_type_denoter()?;
+              self.accept(TokenType::Tilde)?;
+              let body = self.parse_expression()?;
+              Ok(ASTNode::Func(name, params, return_type, Box::new(body)))
+          }
+          TokenType::Type => {
+              self.next_token();
+              let name = self.parse_identifier()?;
+              self.accept(TokenType::Tilde)?;
+              let type_denoter = self.parse_type_denoter()?;
+              Ok(ASTNode::Type(name, type_denoter))
+          }
+          TokenType::Proc => {
+              self.next_token();
+              let name = self.parse_identifier()?;
+              self.accept(TokenType::LeftParen)?;
+              let params = self.parse_formal_parameter_sequence()?;
+              self.accept(TokenType::RightParen)?;
+              self.accept(TokenType::Tilde)?;
+              let body = self.parse_single_command()?;
+              Ok(ASTNode::Proc(name, params, Box::new(body)))
+          }
+          _ => Err(SyntaxError::UnexpectedToken {
+              expected: TokenType::Const,
+              found: self.current_token.token_type.clone(),
+              row: self.current_token.row,
+              col: self.current_token.col,
+          }),
+      }
+  }
+
+  // Después de `func`: un operador binario tiene dos parámetros y uno unario, uno
+  fn parse_operator_declaration(&mut self) -> Result<ASTNode, SyntaxError> {
+      let op = self.current_token.lexeme.to_string();
+      self.next_token();
+      self.accept(TokenType::LeftParen)?;
+      let first = self.parse_formal_parameter()?;
+      let second = if self.current_token.token_type == TokenType::Comma {
+          self.next_token();
+          Some(self.parse_formal_parameter()?)
+      } else {
+          None
+      };
+      self.accept(TokenType::RightParen)?;
+      self.accept(TokenType::Colon)?;
+      let return_type = self.parse_type_denoter()?;
+      self.accept(TokenType::Tilde)?;
+      let body = Box::new(self.parse_expression()?);
+      match second {
+          Some(second) => Ok(ASTNode::BinaryOperatorDeclaration(
+              op,
+              Box::new(first),
+              Box::new(second),
+              return_type,
+              body,
+          )),
+          None => Ok(ASTNode::UnaryOperatorDeclaration(op, Box::new(first), return_type, body)),
+      }
+  }
+
+  fn parse_primary_expression(&mut self) -> Result<ASTNode, SyntaxError> {
+      match self.current_token.token_type {
+          TokenType::IntegerLiteral => {
+              let value = self.parse_integer_literal()?;
+              Ok(ASTNode::Number(value))
+          }
+          TokenType::CharLiteral => {
+              let value = self.parse_char_literal()?;
+              Ok(ASTNode::Char(value))
+          }
+          TokenType::Identifier => {
+              let name = self.parse_identifier()?;
+              if self.current_token.token_type == TokenType::LeftParen {
+                  self.next_token();
+                  let params = self.parse_actual_parameter_sequence()?;
+                  self.accept(TokenType::RightParen)?;
+                  Ok(ASTNode::Call(name, params))
+              } else {
+                  Ok(ASTNode::Vname(self.parse_vname_rest(Vname::Simple(name))?))
+              }
+          }
+          TokenType::LeftParen => {
+              self.next_token();
+              let expr = self.parse_expression()?;
+              self.accept(TokenType::RightParen)?;
+              Ok(ASTNode::Expression(Box::new(expr)))
+          }
+          TokenType::LeftBracket => {
+              self.next_token();
+              let mut elements = vec![self.parse_expression()?];
+              while self.current_token.token_type == TokenType::Comma {
+                  self.next_token();
+                  elements.push(self.parse_expression()?);
+              }
+              self.accept(TokenType::RightBracket)?;
+              Ok(ASTNode::ArrayAggregate(elements))
+          }
+          TokenType::LeftBrace => {
+              self.next_token();
+              let mut fields = vec![self.parse_field_aggregate()?];
+              while self.current_token.token_type == TokenType::Comma {
+                  self.next_token();
+                  fields.push(self.parse_field_aggregate()?);
+              }
+              self.accept(TokenType::RightBrace)?;
+              Ok(ASTNode::RecordAggregate(fields))
+          }
+          _ => Err(SyntaxError::UnexpectedToken {
+              expected: TokenType::Identifier,
+              found: self.current_token.token_type.clone(),
+              row: self.current_token.row,
+              col: self.current_token.col,
+          }),
+      }
+  }
+
+  fn parse_field_aggregate(&mut self) -> Result<(String, ASTNode), SyntaxError> {
+      let name = self.parse_identifier()?;
+      self.accept(TokenType::Tilde)?;
+      let value = self.parse_expression()?;
+      Ok((name, value))
+  }
+
+  fn parse_second_expression(&mut self) -> Result<ASTNode, SyntaxError> {
+      self.parse_binary_expression(0)
+  }
+
+  // Precedence climbing: agrupa los operadores con nivel mayor o igual a
+  // `min_level` según la tabla de operadores
+  fn parse_binary_expression(&mut self, min_level: u8) -> Result<ASTNode, SyntaxError> {
+      let mut left = self.parse_unary_expression()?;
+      while self.current_token.operator_kind().is_some() {
+          let op = self.current_token.lexeme.to_string();
+          let precedence = self.operators.precedence(&op);
+          if precedence.level < min_level {
+              break;
+          }
+          self.next_token();
+          let next_level = match precedence.associativity {
+              Associativity::Left => precedence.level + 1,
+              Associativity::Right => precedence.level,
+          };
+          let right = self.parse_binary_expression(next_level)?;
+          left = ASTNode::Operator(op, Box::new(left), Box::new(right));
+      }
+      Ok(left)
+  }
+
+  // Un operador antes de una expresión primaria es unario (`-x`, `\b`); `=` solo es binario
+  fn parse_unary_expression(&mut self) -> Result<ASTNode, SyntaxError> {
+      if self.current_token.token_type == TokenType::Operator {
+          let op = self.current_token.lexeme.to_string();
+          self.next_token();
+          let operand = self.parse_unary_expression()?;
+          Ok(ASTNode::UnaryOperator(op, Box::new(operand)))
+      } else {
+          self.parse_primary_expression()
+      }
+  }
+
+  fn parse_integer_literal(&mut self) -> Result<i64, SyntaxError> {
+      if self.current_token.token_type != TokenType::IntegerLiteral {
+          return Err(SyntaxError::UnexpectedToken {
+              expected: TokenType::IntegerLiteral,
+              found: self.current_token.token_type.clone(),
+              row: self.current_token.row,
+              col: self.current_token.col,
+          });
+      }
+      match self.word_size.parse_literal(&self.current_token.lexeme) {
+          Some(value) => {
+              self.next_token();
+              Ok(value)
+          }
+          None => Err(SyntaxError::IntegerOutOfRange {
+              lexeme: self.current_token.lexeme.to_string(),
+              maxint: self.word_size.maxint(),
+              row: self.current_token.row,
+              col: self.current_token.col,
+          }),
+      }
+  }
+
+  fn parse_char_literal(&mut self) -> Result<char, SyntaxError> {
+      let mut chars = self.current_token.lexeme.chars();
+      match (chars.next(), chars.next()) {
+          (Some(value), None) => {
+              self.next_token();
+              Ok(value)
+          }
+          _ => Err(SyntaxError::InvalidCharLiteral {
+              lexeme: self.current_token.lexeme.to_string(),
+              row: self.current_token.row,
+              col: self.current_token.col,
+          }),
+      }
+  }
+
+  fn parse_type_denoter(&mut self) -> Result<TypeDenoter, SyntaxError> {
+      match self.current_token.token_type {
+          TokenType::Array => {
+              self.next_token();
+              let size = self.parse_integer_literal()?;
+              self.accept(TokenType::Of)?;
+              let element_type = self.parse_type_denoter()?;
+              Ok(TypeDenoter::Array(size, Box::new(element_type)))
+          }
+          TokenType::Record => {
+              self.next_token();
+              let mut fields = vec![self.parse_field_type()?];
+              while self.current_token.token_type == TokenType::Comma {
+                  self.next_token();
+                  fields.push(self.parse_field_type()?);
+              }
+              self.accept(TokenType::End)?;
+              Ok(TypeDenoter::Record(fields))
+          }
+          _ => Ok(TypeDenoter::Named(self.parse_identifier()?)),
+      }
+  }
+
+  fn parse_field_type(&mut self) -> Result<(String, TypeDenoter), SyntaxError> {
+      let name = self.parse_identifier()?;
+      self.accept(TokenType::Colon)?;
+      let type_denoter = self.parse_type_denoter()?;
+      Ok((name, type_denoter))
+  }
+
+  // Selecciones de campo (`.campo`) e índices (`[expr]`) después del nombre
+  fn parse_vname_rest(&mut self, mut vname: Vname) -> Result<Vname, SyntaxError> {
+      loop {
+          match self.current_token.token_type {
+              TokenType::Period => {
+                  self.next_token();
+                  let field = self.parse_identifier()?;
+                  vname = Vname::Field(Box::new(vname), field);
+              }
+              TokenType::LeftBracket => {
+                  self.next_token();
+                  let index = self.parse_expression()?;
+                  self.accept(TokenType::RightBracket)?;
+                  vname = Vname::Index(Box::new(vname), Box::new(index));
+              }
+              _ => return Ok(vname),
+          }
+      }
+  }
+
+  fn parse_identifier(&mut self) -> Result<String, SyntaxError> {
+      if let TokenType::Identifier = self.current_token.token_type {
+          let name = self.current_token.lexeme.to_string();
+          self.next_token();
+          Ok(name)
+      } else {
+          Err(SyntaxError::UnexpectedToken {
+              expected: TokenType::Identifier,
+              found: self.current_token.token_type.clone(),
+              row: self.current_token.row,
+              col: self.current_token.col,
+          })
+      }
+  }
+
+  fn parse_formal_parameter_sequence(&mut self) -> Result<Vec<ASTNode>, SyntaxError> {
+      let mut params = Vec::new();
+      if self.current_token.token_type != TokenType::RightParen {
+          params.push(self.parse_formal_parameter()?);
+          while self.current_token.token_type == TokenType::Comma {
+              self.next_token();
+              params.push(self.parse_formal_parameter()?);
+          }
+      }
+      Ok(params)
+  }
+
+  fn parse_formal_parameter(&mut self) -> Result<ASTNode, SyntaxError> {
+      if self.current_token.token_type == TokenType::Var {
+          self.next_token();
+          let name = self.parse_identifier()?;
+          self.accept(TokenType::Colon)?;
+          let type_denoter = self.parse_type_denoter()?;
+          Ok(ASTNode::VarParameter(name, type_denoter))
+      } else {
+          let name = self.parse_identifier()?;
+          self.accept(TokenType::Colon)?;
+          let type_denoter = self.parse_type_denoter()?;
+          Ok(ASTNode::ValueParameter(name, type_denoter))
+      }
+  }
+
+  fn parse_actual_parameter_sequence(&mut self) -> Result<Vec<ASTNode>, SyntaxError> {
+      let mut params = Vec::new();
+      if self.current_token.token_type != TokenType::RightParen {
+          params.push(self.parse_expression()?);
+          while self.current_token.token_type == TokenType::Comma {
+              self.next_token();
+              params.push(self.parse_expression()?);
+          }
+      }
+      Ok(params)
+  }
+}
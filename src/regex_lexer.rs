@@ -0,0 +1,234 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Lexer alternativo generado a partir de una especificación declarativa: una
+// lista de reglas `(TokenType, patrón)`. En cada posición gana la regla con la
+// coincidencia más larga y, si hay empate, la que se agregó primero. Así un
+// dialecto puede agregar tokens sin modificar `Lexer::next_token`.
+//
+// `TokenSpec::triangle` reproduce el lexer escrito a mano (sin trivia ni
+// comentarios de bloque): ambos producen la misma secuencia de tokens para
+// programas léxicamente correctos.
+
+use std::borrow::Cow;
+
+use regex::Regex;
+
+use crate::keywords::KeywordTable;
+use crate::lexer::LexError;
+use crate::source_map::{SourceMap, Span};
+use crate::token::{Token, TokenType};
+use crate::word_size::WordSize;
+
+// `'\'` (la barra invertida) va al final: `'\''` es la comilla escapada
+const CHAR_LITERAL: &str = r"'(?:''|\\[ntr0'\\]|[^'\\\n])'|'''|'\\'";
+
+#[derive(Debug, Clone)]
+struct Rule {
+    // `None`: la coincidencia se descarta (espacios y comentarios)
+    token_type: Option<TokenType>,
+    regex: Regex,
+}
+
+impl Rule {
+    fn new(token_type: Option<TokenType>, pattern: &str) -> Result<Rule, regex::Error> {
+        // Anclado al inicio del texto restante
+        let regex = Regex::new(&format!(r"\A(?:{})", pattern))?;
+        Ok(Rule { token_type, regex })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TokenSpec {
+    rules: Vec<Rule>,
+}
+
+impl TokenSpec {
+    pub fn new() -> Self {
+        TokenSpec::default()
+    }
+
+    // Especificación de Triangle con las palabras reservadas de `keywords`
+    pub fn triangle(keywords: &KeywordTable) -> Self {
+        let mut spec = TokenSpec::new();
+        let rules: &[(Option<TokenType>, &str)] = &[
+            (None, r"\s+"),
+            (None, r"![^\n]*"),
+            (Some(TokenType::IntegerLiteral), r"[0-9]+"),
+            (Some(TokenType::CharLiteral), CHAR_LITERAL),
+            (Some(TokenType::Assign), r":="),
+            (Some(TokenType::Colon), r":"),
+            (Some(TokenType::Semicolon), r";"),
+            (Some(TokenType::Comma), r","),
+            (Some(TokenType::Period), r"\."),
+            (Some(TokenType::Equals), r"="),
+            (Some(TokenType::Tilde), r"~"),
+            (Some(TokenType::LeftParen), r"\("),
+            (Some(TokenType::RightParen), r"\)"),
+            (Some(TokenType::LeftBracket), r"\["),
+            (Some(TokenType::RightBracket), r"\]"),
+            (Some(TokenType::LeftBrace), r"\{"),
+            (Some(TokenType::RightBrace), r"\}"),
+            // Un operador no puede empezar con `=` (ese carácter solo es `Equals`)
+            (Some(TokenType::Operator), r"[+\-*/<>&@%^?\\][+\-*/=<>&@%^?\\]*"),
+        ];
+        for (token_type, pattern) in rules {
+            spec.push(token_type.clone(), pattern)
+                .expect("invalid built-in token pattern");
+        }
+        // Las palabras reservadas van antes que los identificadores para ganar los empates
+        for (spelling, token_type) in keywords.iter() {
+            spec.push(Some(token_type.clone()), &regex::escape(spelling))
+                .expect("invalid keyword pattern");
+        }
+        spec.push(Some(TokenType::Identifier), r"\p{Alphabetic}[\p{Alphabetic}0-9\p{M}]*")
+            .expect("invalid built-in token pattern");
+        spec
+    }
+
+    // Agrega una regla con menor prioridad que las anteriores
+    pub fn add(&mut self, token_type: TokenType, pattern: &str) -> Result<(), regex::Error> {
+        self.push(Some(token_type), pattern)
+    }
+
+    // Agrega una regla con mayor prioridad que todas las anteriores, de modo que
+    // gana los empates (por ejemplo, una palabra reservada nueva frente a los
+    // identificadores de igual longitud)
+    pub fn add_first(&mut self, token_type: TokenType, pattern: &str) -> Result<(), regex::Error> {
+        let rule = Rule::new(Some(token_type), pattern)?;
+        self.rules.insert(0, rule);
+        Ok(())
+    }
+
+    // Agrega una regla cuyas coincidencias se descartan
+    pub fn add_skip(&mut self, pattern: &str) -> Result<(), regex::Error> {
+        self.push(None, pattern)
+    }
+
+    fn push(&mut self, token_type: Option<TokenType>, pattern: &str) -> Result<(), regex::Error> {
+        let rule = Rule::new(token_type, pattern)?;
+        self.rules.push(rule);
+        Ok(())
+    }
+
+    // Regla ganadora en el inicio de `input` y longitud de la coincidencia
+    fn longest_match(&self, input: &str) -> Option<(&Rule, usize)> {
+        let mut best: Option<(&Rule, usize)> = None;
+        for rule in &self.rules {
+            if let Some(found) = rule.regex.find(input) {
+                let len = found.end();
+                if len > 0 && best.is_none_or(|(_, best_len)| len > best_len) {
+                    best = Some((rule, len));
+                }
+            }
+        }
+        best
+    }
+}
+
+pub struct RegexLexer<'a> {
+    spec: &'a TokenSpec,
+    source_map: SourceMap<'a>,
+    pos: usize,
+    word_size: WordSize,
+    errors: Vec<LexError>,
+    finished: bool,
+}
+
+impl<'a> RegexLexer<'a> {
+    pub fn new(spec: &'a TokenSpec, input: impl Into<Cow<'a, str>>) -> RegexLexer<'a> {
+        RegexLexer {
+            spec,
+            source_map: SourceMap::new(input),
+            pos: 0,
+            word_size: WordSize::default(),
+            errors: Vec::new(),
+            finished: false,
+        }
+    }
+
+    pub fn with_word_size(mut self, word_size: WordSize) -> Self {
+        self.word_size = word_size;
+        self
+    }
+
+    pub fn errors(&self) -> &[LexError] {
+        &self.errors
+    }
+
+    pub fn next_token(&mut self) -> Token<'a> {
+        loop {
+            let start = self.pos;
+            let rest = &self.source_map.source()[start..];
+            let (row, col) = self.source_map.line_col(start);
+
+            let Some(ch) = rest.chars().next() else {
+                self.finished = true;
+                return Token::new(TokenType::EOF, "", row, col).with_span(Span::new(start, start));
+            };
+
+            let (token_type, len) = match self.spec.longest_match(rest) {
+                Some((rule, len)) => (rule.token_type.clone(), len),
+                None => {
+                    self.errors.push(LexError::IllegalCharacter { character: ch, row, col });
+                    (Some(TokenType::Illegal), ch.len_utf8())
+                }
+            };
+            self.pos += len;
+
+            let Some(token_type) = token_type else {
+                continue;
+            };
+            let span = Span::new(start, self.pos);
+            let mut lexeme = self.source_map.slice(span);
+            match token_type {
+                TokenType::CharLiteral => lexeme = decode_char_literal(lexeme),
+                TokenType::IntegerLiteral if self.word_size.parse_literal(&lexeme).is_none() => {
+                    self.errors.push(LexError::IntegerTooLarge {
+                        lexeme: lexeme.to_string(),
+                        maxint: self.word_size.maxint(),
+                        row,
+                        col,
+                    });
+                }
+                _ => {}
+            }
+            return Token::new(token_type, lexeme, row, col).with_span(span);
+        }
+    }
+}
+
+impl<'a> Iterator for RegexLexer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            None
+        } else {
+            Some(self.next_token())
+        }
+    }
+}
+
+// Valor de un literal de carácter ya reconocido por `CHAR_LITERAL`; un
+// carácter sin escape se sigue tomando prestado del código fuente
+fn decode_char_literal(literal: Cow<str>) -> Cow<str> {
+    let decoded = match &literal[1..literal.len() - 1] {
+        "''" | "'" => '\'',
+        "\\n" => '\n',
+        "\\t" => '\t',
+        "\\r" => '\r',
+        "\\0" => '\0',
+        "\\'" => '\'',
+        "\\\\" | "\\" => '\\',
+        _ => {
+            return match literal {
+                Cow::Borrowed(literal) => Cow::Borrowed(&literal[1..literal.len() - 1]),
+                Cow::Owned(literal) => Cow::Owned(literal[1..literal.len() - 1].to_string()),
+            }
+        }
+    };
+    Cow::Owned(decoded.to_string())
+}
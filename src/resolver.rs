@@ -0,0 +1,202 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Resolución de operadores: enlaza cada aplicación de un operador del árbol
+// (`ASTNode::Operator` o `ASTNode::UnaryOperator`) con la declaración que la
+// define. Las declaraciones de un bloque `let` son visibles en las declaraciones
+// siguientes, en su propio cuerpo (recursión) y en el cuerpo del bloque; la más
+// interna oculta a las demás. Si no hay ninguna, el operador debe pertenecer al
+// ambiente estándar.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ast::{ASTNode, Vname};
+use crate::token::OperatorKind;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OperatorBinding<'a> {
+    Standard(OperatorKind),
+    // `BinaryOperatorDeclaration` o `UnaryOperatorDeclaration`
+    Declared(&'a ASTNode),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    UndeclaredOperator { operator: String, arity: usize },
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResolveError::UndeclaredOperator { operator, arity: 1 } => {
+                write!(f, "Undeclared unary operator: {}", operator)
+            }
+            ResolveError::UndeclaredOperator { operator, .. } => {
+                write!(f, "Undeclared binary operator: {}", operator)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+#[derive(Debug, Default)]
+pub struct Resolution<'a> {
+    bindings: Vec<(&'a ASTNode, OperatorBinding<'a>)>,
+    // Las mismas declaraciones indexadas por la dirección del nodo de la aplicación
+    by_node: HashMap<*const ASTNode, OperatorBinding<'a>>,
+}
+
+impl<'a> Resolution<'a> {
+    // Declaración de una aplicación de operador (se compara por identidad del nodo)
+    pub fn binding(&self, application: &ASTNode) -> Option<OperatorBinding<'a>> {
+        self.by_node.get(&(application as *const ASTNode)).copied()
+    }
+
+    // Aplicaciones en el orden en que aparecen en el árbol
+    pub fn iter(&self) -> impl Iterator<Item = &(&'a ASTNode, OperatorBinding<'a>)> {
+        self.bindings.iter()
+    }
+
+    fn insert(&mut self, application: &'a ASTNode, binding: OperatorBinding<'a>) {
+        self.by_node.insert(application, binding);
+        self.bindings.push((application, binding));
+    }
+}
+
+pub fn resolve_operators(ast: &ASTNode) -> Result<Resolution<'_>, ResolveError> {
+    let mut resolver = Resolver {
+        scope: Vec::new(),
+        resolution: Resolution::default(),
+    };
+    resolver.visit(ast)?;
+    Ok(resolver.resolution)
+}
+
+struct Resolver<'a> {
+    // Declaraciones de operadores visibles, de la más externa a la más interna
+    scope: Vec<&'a ASTNode>,
+    resolution: Resolution<'a>,
+}
+
+impl<'a> Resolver<'a> {
+    fn declare(&mut self, declaration: &'a ASTNode) -> Result<(), ResolveError> {
+        match declaration {
+            ASTNode::Declaration(declarations) => {
+                for declaration in declarations {
+                    self.declare(declaration)?;
+                }
+                Ok(())
+            }
+            ASTNode::BinaryOperatorDeclaration(..) | ASTNode::UnaryOperatorDeclaration(..) => {
+                self.scope.push(declaration);
+                self.visit(declaration)
+            }
+            other => self.visit(other),
+        }
+    }
+
+    fn lookup(&self, operator: &str, arity: usize) -> Option<OperatorBinding<'a>> {
+        let declared = self.scope.iter().rev().find(|declaration| match declaration {
+            ASTNode::BinaryOperatorDeclaration(name, ..) => arity == 2 && name == operator,
+            ASTNode::UnaryOperatorDeclaration(name, ..) => arity == 1 && name == operator,
+            _ => false,
+        });
+        if let Some(declaration) = declared {
+            return Some(OperatorBinding::Declared(declaration));
+        }
+
+        let kind = OperatorKind::from_lexeme(operator);
+        let standard = match kind {
+            OperatorKind::UserDefined => false,
+            OperatorKind::Not => arity == 1,
+            OperatorKind::Minus => true,
+            _ => arity == 2,
+        };
+        standard.then_some(OperatorBinding::Standard(kind))
+    }
+
+    fn apply(&mut self, application: &'a ASTNode, operator: &str, arity: usize) -> Result<(), ResolveError> {
+        let binding = self
+            .lookup(operator, arity)
+            .ok_or_else(|| ResolveError::UndeclaredOperator {
+                operator: operator.to_string(),
+                arity,
+            })?;
+        self.resolution.insert(application, binding);
+        Ok(())
+    }
+
+    fn visit(&mut self, node: &'a ASTNode) -> Result<(), ResolveError> {
+        match node {
+            ASTNode::Let(declarations, body) => {
+                let outer = self.scope.len();
+                self.declare(declarations)?;
+                self.visit(body)?;
+                self.scope.truncate(outer);
+            }
+            ASTNode::Operator(operator, left, right) => {
+                self.apply(node, operator, 2)?;
+                self.visit(left)?;
+                self.visit(right)?;
+            }
+            ASTNode::UnaryOperator(operator, operand) => {
+                self.apply(node, operator, 1)?;
+                self.visit(operand)?;
+            }
+            ASTNode::Const(_, expr) | ASTNode::Expression(expr) => self.visit(expr)?,
+            ASTNode::Func(_, _, _, body)
+            | ASTNode::Proc(_, _, body)
+            | ASTNode::BinaryOperatorDeclaration(_, _, _, _, body)
+            | ASTNode::UnaryOperatorDeclaration(_, _, _, body) => self.visit(body)?,
+            ASTNode::Assign(vname, expr) => {
+                self.visit_vname(vname)?;
+                self.visit(expr)?;
+            }
+            ASTNode::If(condition, _, then_branch, _, else_branch) => {
+                self.visit(condition)?;
+                self.visit(then_branch)?;
+                self.visit(else_branch)?;
+            }
+            ASTNode::While(condition, body) => {
+                self.visit(condition)?;
+                self.visit(body)?;
+            }
+            ASTNode::Vname(vname) => self.visit_vname(vname)?,
+            ASTNode::Call(_, nodes)
+            | ASTNode::ArrayAggregate(nodes)
+            | ASTNode::Command(nodes) => {
+                for node in nodes {
+                    self.visit(node)?;
+                }
+            }
+            ASTNode::RecordAggregate(fields) => {
+                for (_, value) in fields {
+                    self.visit(value)?;
+                }
+            }
+            ASTNode::Declaration(_) => self.declare(node)?,
+            ASTNode::Var(..)
+            | ASTNode::ValueParameter(..)
+            | ASTNode::VarParameter(..)
+            | ASTNode::Type(..)
+            | ASTNode::Number(_)
+            | ASTNode::Char(_) => {}
+        }
+        Ok(())
+    }
+
+    fn visit_vname(&mut self, vname: &'a Vname) -> Result<(), ResolveError> {
+        match vname {
+            Vname::Simple(_) => Ok(()),
+            Vname::Field(record, _) => self.visit_vname(record),
+            Vname::Index(array, index) => {
+                self.visit_vname(array)?;
+                self.visit(index)
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Posiciones exactas en el código fuente: cada token guarda un `Span` con
+// offsets en bytes y el `SourceMap` los convierte a línea/columna (base 1).
+
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceMap<'a> {
+    source: Cow<'a, str>,
+    // Offset en bytes donde empieza cada línea
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+    pub fn new(source: impl Into<Cow<'a, str>>) -> Self {
+        let mut source_map = SourceMap {
+            source: Cow::Borrowed(""),
+            line_starts: vec![0],
+        };
+        let source = source.into();
+        source_map.index_lines(&source, 0);
+        source_map.source = source;
+        source_map
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    // Agrega texto al final (el lexer lo usa al leer de un `BufRead`)
+    pub fn push_str(&mut self, text: &str) {
+        let offset = self.source.len();
+        self.source.to_mut().push_str(text);
+        self.index_lines(text, offset);
+    }
+
+    // Texto de un span; si el código fuente es prestado no se copia, si es propio
+    // (`String` o `BufRead`) se devuelve una copia
+    pub fn slice(&self, span: Span) -> Cow<'a, str> {
+        match self.source {
+            Cow::Borrowed(source) => Cow::Borrowed(&source[span.start..span.end]),
+            Cow::Owned(ref source) => Cow::Owned(source[span.start..span.end].to_string()),
+        }
+    }
+
+    fn index_lines(&mut self, text: &str, offset: usize) {
+        self.line_starts.extend(
+            text.bytes()
+                .enumerate()
+                .filter(|(_, byte)| *byte == b'\n')
+                .map(|(i, _)| offset + i + 1),
+        );
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    // Línea (base 1) que contiene el offset
+    pub fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset)
+    }
+
+    pub fn line_start(&self, line: usize) -> usize {
+        self.line_starts[line - 1]
+    }
+
+    // Línea y columna (base 1) contando caracteres Unicode. Un offset dentro de
+    // un carácter de varios bytes da la columna de ese carácter.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let line = self.line(offset);
+        let col = self.line_prefix(line, offset).chars().count() + 1;
+        (line, col)
+    }
+
+    // Línea y columna (base 1) contando unidades UTF-16, como lo hacen los editores
+    pub fn line_col_utf16(&self, offset: usize) -> (usize, usize) {
+        let line = self.line(offset);
+        let col = self.line_prefix(line, offset).encode_utf16().count() + 1;
+        (line, col)
+    }
+
+    // Inverso de `line_col`: offset en bytes de una línea y columna (base 1), o
+    // `None` si la columna queda más allá del final de la línea. El salto de
+    // línea es la última columna de su línea; la última línea termina al final
+    // del archivo.
+    pub fn offset(&self, line: usize, col: usize) -> Option<usize> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let (end, eof) = match self.line_starts.get(line) {
+            Some(&next_start) => (next_start, None),
+            None => (self.source.len(), Some(self.source.len())),
+        };
+        self.source[start..end]
+            .char_indices()
+            .map(|(i, _)| start + i)
+            .chain(eof)
+            .nth(col.checked_sub(1)?)
+    }
+
+    // Texto de la línea hasta `offset`, retrocediendo al inicio del carácter si
+    // `offset` cae dentro de uno
+    fn line_prefix(&self, line: usize, offset: usize) -> &str {
+        let mut offset = offset.min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        &self.source[self.line_start(line)..offset]
+    }
+}
@@ -0,0 +1,365 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::lexer::{is_identifier_char, is_operator_char};
+use crate::source_map::Span;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TokenType {
+    EOF,
+    Illegal,
+    Identifier,
+    IntegerLiteral,
+    CharLiteral,
+    Operator,
+    Array,
+    Begin,
+    Const,
+    Do,
+    Else,
+    End,
+    Func,
+    If,
+    In,
+    Let,
+    Of,
+    Proc,
+    Record,
+    Then,
+    Type,
+    Var,
+    While,
+    Period,
+    Colon,
+    Semicolon,
+    Comma,
+    Equals,
+    Tilde,
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    LeftBrace,
+    RightBrace,
+    Assign,
+}
+
+// El lexema se toma prestado del código fuente cuando el lexer trabaja sobre
+// un `&str`; los tokens leídos de archivos o de un `String` lo tienen propio.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub token_type: TokenType,
+    pub lexeme: Cow<'a, str>,
+    pub row: usize,
+    pub col: usize,
+    pub span: Span,
+    // Trivia y escritura original: la mayoría de los tokens no las tienen, así
+    // que van en una caja aparte para no agrandar cada token
+    extra: Option<Box<TokenExtra<'a>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct TokenExtra<'a> {
+    // Solo en modo trivia (`Lexer::with_trivia`): comentarios y espacios previos
+    leading_trivia: Vec<Trivia<'a>>,
+    // Texto en el código fuente si difiere del lexema: literales de carácter
+    // como '\n' o '''' (modo trivia) e identificadores normalizados
+    source_text: Option<Cow<'a, str>>,
+}
+
+impl<'a> Token<'a> {
+    pub fn new(token_type: TokenType, lexeme: impl Into<Cow<'a, str>>, row: usize, col: usize) -> Self {
+        Token {
+            token_type,
+            lexeme: lexeme.into(),
+            row,
+            col,
+            span: Span::default(),
+            extra: None,
+        }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    pub fn with_source_text(mut self, text: impl Into<Cow<'a, str>>) -> Self {
+        self.extra.get_or_insert_with(Box::default).source_text = Some(text.into());
+        self
+    }
+
+    pub fn source_text(&self) -> Option<&str> {
+        self.extra.as_ref()?.source_text.as_deref()
+    }
+
+    pub fn leading_trivia(&self) -> &[Trivia<'a>] {
+        match &self.extra {
+            Some(extra) => &extra.leading_trivia,
+            None => &[],
+        }
+    }
+
+    pub fn leading_trivia_mut(&mut self) -> &mut [Trivia<'a>] {
+        match &mut self.extra {
+            Some(extra) => &mut extra.leading_trivia,
+            None => &mut [],
+        }
+    }
+
+    pub fn set_leading_trivia(&mut self, trivia: Vec<Trivia<'a>>) {
+        if !trivia.is_empty() || self.extra.is_some() {
+            self.extra.get_or_insert_with(Box::default).leading_trivia = trivia;
+        }
+    }
+
+    // Clase del operador si el token es un operador (incluye `=`, que se lexea como `Equals`)
+    pub fn operator_kind(&self) -> Option<OperatorKind> {
+        match self.token_type {
+            TokenType::Operator | TokenType::Equals => Some(OperatorKind::from_lexeme(&self.lexeme)),
+            _ => None,
+        }
+    }
+
+    // Texto del token tal como aparece en el código fuente
+    pub fn text(&self) -> &str {
+        self.source_text().unwrap_or(&self.lexeme)
+    }
+
+    pub fn into_owned(self) -> Token<'static> {
+        Token {
+            token_type: self.token_type,
+            lexeme: Cow::Owned(self.lexeme.into_owned()),
+            row: self.row,
+            col: self.col,
+            span: self.span,
+            extra: self.extra.map(|extra| {
+                Box::new(TokenExtra {
+                    leading_trivia: extra.leading_trivia.into_iter().map(Trivia::into_owned).collect(),
+                    source_text: extra.source_text.map(|text| Cow::Owned(text.into_owned())),
+                })
+            }),
+        }
+    }
+}
+
+// Clasificación de los operadores de Triangle. Cualquier otra secuencia de
+// caracteres de operador es válida (operadores declarados por el usuario) y
+// se clasifica como `UserDefined`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperatorKind {
+    Plus,         // +
+    Minus,        // -
+    Times,        // *
+    Divide,       // /
+    Modulo,       // //
+    Less,         // <
+    LessEqual,    // <=
+    Greater,      // >
+    GreaterEqual, // >=
+    Equal,        // =
+    NotEqual,     // \=
+    And,          // /\
+    Or,           // \/
+    Not,          // \
+    UserDefined,
+}
+
+impl OperatorKind {
+    pub fn from_lexeme(lexeme: &str) -> OperatorKind {
+        match lexeme {
+            "+" => OperatorKind::Plus,
+            "-" => OperatorKind::Minus,
+            "*" => OperatorKind::Times,
+            "/" => OperatorKind::Divide,
+            "//" => OperatorKind::Modulo,
+            "<" => OperatorKind::Less,
+            "<=" => OperatorKind::LessEqual,
+            ">" => OperatorKind::Greater,
+            ">=" => OperatorKind::GreaterEqual,
+            "=" => OperatorKind::Equal,
+            "\\=" => OperatorKind::NotEqual,
+            "/\\" => OperatorKind::And,
+            "\\/" => OperatorKind::Or,
+            "\\" => OperatorKind::Not,
+            _ => OperatorKind::UserDefined,
+        }
+    }
+
+    // Escritura estándar del operador (`None` para los definidos por el usuario)
+    pub fn symbol(&self) -> Option<&'static str> {
+        let symbol = match self {
+            OperatorKind::Plus => "+",
+            OperatorKind::Minus => "-",
+            OperatorKind::Times => "*",
+            OperatorKind::Divide => "/",
+            OperatorKind::Modulo => "//",
+            OperatorKind::Less => "<",
+            OperatorKind::LessEqual => "<=",
+            OperatorKind::Greater => ">",
+            OperatorKind::GreaterEqual => ">=",
+            OperatorKind::Equal => "=",
+            OperatorKind::NotEqual => "\\=",
+            OperatorKind::And => "/\\",
+            OperatorKind::Or => "\\/",
+            OperatorKind::Not => "\\",
+            OperatorKind::UserDefined => return None,
+        };
+        Some(symbol)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    Whitespace,
+    Comment,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia<'a> {
+    pub kind: TriviaKind,
+    pub text: Cow<'a, str>,
+    pub span: Span,
+}
+
+impl Trivia<'_> {
+    pub fn into_owned(self) -> Trivia<'static> {
+        Trivia {
+            kind: self.kind,
+            text: Cow::Owned(self.text.into_owned()),
+            span: self.span,
+        }
+    }
+}
+
+// Reconstruye el código fuente a partir de tokens obtenidos en modo trivia
+pub fn to_source(tokens: &[Token]) -> String {
+    let mut source = String::new();
+    for token in tokens {
+        for trivia in token.leading_trivia() {
+            source.push_str(&trivia.text);
+        }
+        source.push_str(token.text());
+    }
+    source
+}
+
+// Reconstruye un programa legible a partir de tokens sin trivia (por ejemplo,
+// leídos de un archivo de tokens): cada token se escribe en la fila y columna
+// registradas. Los comentarios no se recuperan. Si dos tokens se solapan o
+// quedarían pegados formando otro token, se separan con un espacio.
+pub fn detokenize(tokens: &[Token]) -> String {
+    let mut source = String::new();
+    let (mut row, mut col) = (1, 1);
+    let mut last_char = None;
+
+    for token in tokens {
+        if token.token_type == TokenType::EOF {
+            break;
+        }
+        let text = spelling(token);
+        if token.row > row {
+            source.push_str(&"\n".repeat(token.row - row));
+            row = token.row;
+            col = 1;
+            last_char = None;
+        }
+        if token.col > col {
+            source.push_str(&" ".repeat(token.col - col));
+            col = token.col;
+            last_char = None;
+        }
+        if let (Some(previous), Some(next)) = (last_char, text.chars().next()) {
+            if would_merge(previous, next) {
+                source.push(' ');
+                col += 1;
+            }
+        }
+        source.push_str(&text);
+        col += text.chars().count();
+        last_char = text.chars().last().or(last_char);
+    }
+    if !source.is_empty() {
+        source.push('\n');
+    }
+    source
+}
+
+// Escritura de un token en el código fuente; los literales de carácter se
+// vuelven a escribir con comillas y, si hace falta, con secuencias de escape
+fn spelling<'t>(token: &'t Token) -> Cow<'t, str> {
+    if token.token_type != TokenType::CharLiteral {
+        return Cow::Borrowed(token.text());
+    }
+    if let Some(text) = token.source_text() {
+        return Cow::Borrowed(text);
+    }
+    let literal = match token.lexeme.as_ref() {
+        "\n" => "'\\n'".to_string(),
+        "\t" => "'\\t'".to_string(),
+        "\r" => "'\\r'".to_string(),
+        "\0" => "'\\0'".to_string(),
+        "'" => "'\\''".to_string(),
+        "\\" => "'\\\\'".to_string(),
+        lexeme => format!("'{}'", lexeme),
+    };
+    Cow::Owned(literal)
+}
+
+fn would_merge(previous: char, next: char) -> bool {
+    (is_identifier_char(previous) && is_identifier_char(next))
+        || (is_operator_char(previous) && is_operator_char(next))
+        || (previous == ':' && next == '=')
+}
+
+impl FromStr for TokenType {
+    type Err = String;
+
+    // Inverso de `{:?}`: permite leer los tipos escritos en los archivos de tokens
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token_type = match s {
+            "EOF" => TokenType::EOF,
+            "Illegal" => TokenType::Illegal,
+            "Identifier" => TokenType::Identifier,
+            "IntegerLiteral" => TokenType::IntegerLiteral,
+            "CharLiteral" => TokenType::CharLiteral,
+            "Operator" => TokenType::Operator,
+            "Array" => TokenType::Array,
+            "Begin" => TokenType::Begin,
+            "Const" => TokenType::Const,
+            "Do" => TokenType::Do,
+            "Else" => TokenType::Else,
+            "End" => TokenType::End,
+            "Func" => TokenType::Func,
+            "If" => TokenType::If,
+            "In" => TokenType::In,
+            "Let" => TokenType::Let,
+            "Of" => TokenType::Of,
+            "Proc" => TokenType::Proc,
+            "Record" => TokenType::Record,
+            "Then" => TokenType::Then,
+            "Type" => TokenType::Type,
+            "Var" => TokenType::Var,
+            "While" => TokenType::While,
+            "Period" => TokenType::Period,
+            "Colon" => TokenType::Colon,
+            "Semicolon" => TokenType::Semicolon,
+            "Comma" => TokenType::Comma,
+            "Equals" => TokenType::Equals,
+            "Tilde" => TokenType::Tilde,
+            "LeftParen" => TokenType::LeftParen,
+            "RightParen" => TokenType::RightParen,
+            "LeftBracket" => TokenType::LeftBracket,
+            "RightBracket" => TokenType::RightBracket,
+            "LeftBrace" => TokenType::LeftBrace,
+            "RightBrace" => TokenType::RightBrace,
+            "Assign" => TokenType::Assign,
+            _ => return Err(format!("Invalid token type: {}", s)),
+        };
+        Ok(token_type)
+    }
+}
@@ -0,0 +1,176 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Diferencias entre dos secuencias de tokens. Los tokens se alinean por su
+// lexema con el algoritmo de Myers (la menor cantidad de inserciones y
+// eliminaciones); de cada par alineado se reporta si cambió de tipo o de
+// posición.
+
+use std::ops::Range;
+
+use crate::token::Token;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change<'t> {
+    Inserted(&'t Token<'t>),
+    Deleted(&'t Token<'t>),
+    Retyped { old: &'t Token<'t>, new: &'t Token<'t> },
+    Moved { old: &'t Token<'t>, new: &'t Token<'t> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+// Con `ignore_positions` los tokens que solo cambiaron de fila o columna no se reportan
+pub fn diff<'t>(old: &'t [Token<'t>], new: &'t [Token<'t>], ignore_positions: bool) -> Vec<Change<'t>> {
+    let mut changes = Vec::new();
+    for step in align(old, new) {
+        match step {
+            Step::Delete(i) => changes.push(Change::Deleted(&old[i])),
+            Step::Insert(j) => changes.push(Change::Inserted(&new[j])),
+            Step::Equal(i, j) => {
+                let (old, new) = (&old[i], &new[j]);
+                if old.token_type != new.token_type {
+                    changes.push(Change::Retyped { old, new });
+                } else if !ignore_positions && (old.row, old.col) != (new.row, new.col) {
+                    changes.push(Change::Moved { old, new });
+                }
+            }
+        }
+    }
+    changes
+}
+
+// Algoritmo de Myers en espacio lineal: se busca la "serpiente del medio"
+// (el tramo de tokens iguales que está a la mitad del camino más corto)
+// avanzando a la vez desde el inicio y desde el final, y se resuelven por
+// separado las dos mitades. Solo se guardan dos vectores de diagonales, así
+// que la memoria es O(N + M) aunque los archivos sean muy distintos.
+fn align(old: &[Token], new: &[Token]) -> Vec<Step> {
+    let size = 2 * (old.len() + new.len()) + 3;
+    let mut myers = Myers {
+        old,
+        new,
+        forward: vec![0; size],
+        backward: vec![0; size],
+        offset: (old.len() + new.len() + 1) as isize,
+        steps: Vec::new(),
+    };
+    myers.conquer(0..old.len(), 0..new.len());
+    myers.steps
+}
+
+struct Myers<'s, 't> {
+    old: &'s [Token<'t>],
+    new: &'s [Token<'t>],
+    // Posición `x` más lejana alcanzada en cada diagonal `k = x - y`, desde el
+    // inicio y desde el final del rango
+    forward: Vec<usize>,
+    backward: Vec<usize>,
+    offset: isize,
+    steps: Vec<Step>,
+}
+
+impl Myers<'_, '_> {
+    fn same(&self, x: usize, y: usize) -> bool {
+        self.old[x].lexeme == self.new[y].lexeme
+    }
+
+    fn index(&self, k: isize) -> usize {
+        (k + self.offset) as usize
+    }
+
+    fn conquer(&mut self, mut old: Range<usize>, mut new: Range<usize>) {
+        // Prefijo y sufijo comunes
+        while !old.is_empty() && !new.is_empty() && self.same(old.start, new.start) {
+            self.steps.push(Step::Equal(old.start, new.start));
+            old.start += 1;
+            new.start += 1;
+        }
+        let mut suffix = 0;
+        while old.len() > suffix && new.len() > suffix && self.same(old.end - suffix - 1, new.end - suffix - 1) {
+            suffix += 1;
+        }
+        let (old_end, new_end) = (old.end, new.end);
+        old.end -= suffix;
+        new.end -= suffix;
+
+        if old.is_empty() || new.is_empty() {
+            self.steps.extend(old.map(Step::Delete));
+            self.steps.extend(new.map(Step::Insert));
+        } else {
+            let (x, y) = self.middle_snake(old.clone(), new.clone());
+            self.conquer(old.start..x, new.start..y);
+            self.conquer(x..old.end, y..new.end);
+        }
+
+        for i in 0..suffix {
+            self.steps.push(Step::Equal(old_end - suffix + i, new_end - suffix + i));
+        }
+    }
+
+    // Punto (x, y) donde empieza la serpiente del medio; ambos rangos no están vacíos
+    fn middle_snake(&mut self, old: Range<usize>, new: Range<usize>) -> (usize, usize) {
+        let n = old.len() as isize;
+        let m = new.len() as isize;
+        let delta = n - m;
+        let odd = delta % 2 != 0;
+        let (f1, b1) = (self.index(1), self.index(1));
+        self.forward[f1] = 0;
+        self.backward[b1] = 0;
+
+        for d in 0..=(n + m + 1) / 2 {
+            for k in (-d..=d).step_by(2) {
+                let mut x = if k == -d || (k != d && self.forward[self.index(k - 1)] < self.forward[self.index(k + 1)]) {
+                    self.forward[self.index(k + 1)]
+                } else {
+                    self.forward[self.index(k - 1)] + 1
+                } as isize;
+                let (start_x, start_y) = (x, x - k);
+                let mut y = x - k;
+                while x < n && y < m && self.same(old.start + x as usize, new.start + y as usize) {
+                    x += 1;
+                    y += 1;
+                }
+                let slot = self.index(k);
+                self.forward[slot] = x as usize;
+                if odd && (k - delta).abs() < d {
+                    let reverse = self.backward[self.index(delta - k)] as isize;
+                    if x + reverse >= n {
+                        return (old.start + start_x as usize, new.start + start_y as usize);
+                    }
+                }
+            }
+            for k in (-d..=d).step_by(2) {
+                let mut x = if k == -d || (k != d && self.backward[self.index(k - 1)] < self.backward[self.index(k + 1)]) {
+                    self.backward[self.index(k + 1)]
+                } else {
+                    self.backward[self.index(k - 1)] + 1
+                } as isize;
+                let mut y = x - k;
+                while x < n
+                    && y < m
+                    && self.same(old.start + (n - x - 1) as usize, new.start + (m - y - 1) as usize)
+                {
+                    x += 1;
+                    y += 1;
+                }
+                let slot = self.index(k);
+                self.backward[slot] = x as usize;
+                if !odd && (k - delta).abs() <= d {
+                    let forward = self.forward[self.index(delta - k)] as isize;
+                    if x + forward >= n {
+                        return (old.start + (n - x) as usize, new.start + (m - y) as usize);
+                    }
+                }
+            }
+        }
+        unreachable!("the forward and backward searches always meet")
+    }
+}
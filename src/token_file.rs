@@ -0,0 +1,445 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Formato de archivo de tokens (versión 3)
+//
+//   #tokens v3
+//   {Let, 'let', 1, 1, 0, 3}
+//   {CharLiteral, '\'', 2, 9, 20, 24}
+//   {Identifier, 'año', 2, 14, 26, 30, 'Año'}
+//   {EOF, '', 3, 1, 31, 31}
+//
+// La primera línea indica la versión. Cada línea siguiente es un token:
+// tipo, lexema entre comillas simples, fila, columna, el span (offsets en
+// bytes de inicio y fin en el código fuente) y, solo si difiere del lexema,
+// el texto original del token (`Token::source_text`). Dentro del lexema y del
+// texto original se escapan `\\`, `\'`, `\n`, `\r`, `\t` y cualquier otro
+// carácter de control como `\u{XXXX}`, de modo que
+// `read_tokens(write_tokens(tokens)) == tokens` para cualquier lexema.
+//
+// La versión 2 no incluye el texto original y la versión 1 tampoco el span.
+// Los archivos sin encabezado (generados por versiones anteriores de
+// `tokenize`) se leen con el formato original sin escapes; en ese caso y en la
+// versión 1 el span queda vacío.
+//
+// También se pueden escribir y leer tokens en JSON y CSV (ver `Format`);
+// `read_tokens` detecta el formato automáticamente.
+
+mod csv;
+mod json;
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::source_map::Span;
+use crate::token::{Token, TokenType};
+
+pub const VERSION: u32 = 3;
+const HEADER_PREFIX: &str = "#tokens v";
+
+#[derive(Debug)]
+pub enum TokenFileError {
+    Io(io::Error),
+    UnsupportedVersion(String),
+    Malformed { line: usize, message: String },
+}
+
+impl fmt::Display for TokenFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenFileError::Io(err) => write!(f, "{}", err),
+            TokenFileError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported token file version: {}", version)
+            }
+            TokenFileError::Malformed { line, message } => {
+                write!(f, "Malformed token at line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TokenFileError {}
+
+impl From<io::Error> for TokenFileError {
+    fn from(err: io::Error) -> Self {
+        TokenFileError::Io(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format: {} (expected text, json or csv)", s)),
+        }
+    }
+}
+
+impl Format {
+    pub fn detect(content: &str) -> Format {
+        let content = content.trim_start();
+        if content.starts_with(csv::HEADER_V1) {
+            Format::Csv
+        } else if content.starts_with('[')
+            || (content.starts_with('{') && content[1..].trim_start().starts_with('"'))
+        {
+            Format::Json
+        } else {
+            Format::Text
+        }
+    }
+}
+
+pub fn write_header<W: Write>(out: &mut W) -> io::Result<()> {
+    writeln!(out, "{}{}", HEADER_PREFIX, VERSION)
+}
+
+pub fn write_token<W: Write>(out: &mut W, token: &Token) -> io::Result<()> {
+    write!(
+        out,
+        "{{{:?}, '{}', {}, {}, {}, {}",
+        token.token_type,
+        escape_lexeme(&token.lexeme),
+        token.row,
+        token.col,
+        token.span.start,
+        token.span.end
+    )?;
+    if let Some(text) = token.source_text() {
+        write!(out, ", '{}'", escape_lexeme(text))?;
+    }
+    writeln!(out, "}}")
+}
+
+pub fn write_tokens<W: Write>(out: &mut W, tokens: &[Token]) -> io::Result<()> {
+    write_header(out)?;
+    for token in tokens {
+        write_token(out, token)?;
+    }
+    Ok(())
+}
+
+pub fn write_tokens_as<W: Write>(out: &mut W, tokens: &[Token], format: Format) -> io::Result<()> {
+    match format {
+        Format::Text => write_tokens(out, tokens),
+        Format::Json => json::write_tokens(out, tokens),
+        Format::Csv => csv::write_tokens(out, tokens),
+    }
+}
+
+pub fn read_tokens<R: BufRead>(mut reader: R) -> Result<Vec<Token<'static>>, TokenFileError> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    read_tokens_from_str(&content)
+}
+
+pub fn read_tokens_from_str(content: &str) -> Result<Vec<Token<'static>>, TokenFileError> {
+    match Format::detect(content) {
+        Format::Text => read_text(content),
+        Format::Json => json::read_tokens(content),
+        Format::Csv => csv::read_tokens(content),
+    }
+}
+
+fn read_text(content: &str) -> Result<Vec<Token<'static>>, TokenFileError> {
+    let mut tokens = Vec::new();
+    let mut version = 0;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+
+        if index == 0 {
+            if let Some(header) = line.strip_prefix(HEADER_PREFIX) {
+                version = match header.trim().parse() {
+                    Ok(version @ 1..=VERSION) => version,
+                    _ => return Err(TokenFileError::UnsupportedVersion(header.trim().to_string())),
+                };
+                continue;
+            }
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let token = if version == 0 {
+            parse_legacy_line(line)
+        } else {
+            parse_line(line, version)
+        };
+        let token = token.map_err(|message| TokenFileError::Malformed {
+            line: line_number,
+            message,
+        })?;
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+// Problema de un archivo de tokens encontrado por `check`; los índices son
+// la posición del token en el archivo (base 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckError {
+    MissingEof,
+    EofNotLast { index: usize },
+    MultipleEof { count: usize },
+    InvalidPosition { index: usize, row: usize, col: usize },
+    PositionGoesBack { index: usize, row: usize, col: usize, previous_row: usize, previous_col: usize },
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckError::MissingEof => write!(f, "no EOF token"),
+            CheckError::EofNotLast { index } => write!(f, "the EOF token (token {}) is not the last one", index),
+            CheckError::MultipleEof { count } => write!(f, "{} EOF tokens", count),
+            CheckError::InvalidPosition { index, row, col } => {
+                write!(f, "token {}: invalid position {}:{}", index, row, col)
+            }
+            CheckError::PositionGoesBack { index, row, col, previous_row, previous_col } => write!(
+                f,
+                "token {}: position {}:{} comes before the previous token ({}:{})",
+                index, row, col, previous_row, previous_col
+            ),
+        }
+    }
+}
+
+// Problemas de un archivo de tokens ya leído: debe terminar con exactamente un `EOF`
+// y las posiciones deben ser válidas y no decrecientes
+pub fn check(tokens: &[Token]) -> Vec<CheckError> {
+    let mut problems = Vec::new();
+    let eofs: Vec<usize> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| token.token_type == TokenType::EOF)
+        .map(|(index, _)| index)
+        .collect();
+    match eofs.as_slice() {
+        [] => problems.push(CheckError::MissingEof),
+        [index] if *index + 1 == tokens.len() => {}
+        [index] => problems.push(CheckError::EofNotLast { index: index + 1 }),
+        _ => problems.push(CheckError::MultipleEof { count: eofs.len() }),
+    }
+
+    let mut previous: Option<&Token> = None;
+    for (index, token) in tokens.iter().enumerate() {
+        if token.row == 0 || token.col == 0 {
+            problems.push(CheckError::InvalidPosition { index: index + 1, row: token.row, col: token.col });
+        }
+        if let Some(previous) = previous {
+            if (token.row, token.col) < (previous.row, previous.col) {
+                problems.push(CheckError::PositionGoesBack {
+                    index: index + 1,
+                    row: token.row,
+                    col: token.col,
+                    previous_row: previous.row,
+                    previous_col: previous.col,
+                });
+            }
+        }
+        previous = Some(token);
+    }
+    problems
+}
+
+// Selección de tokens por tipo y por rango de líneas (`tokens --type` y `--lines`);
+// sin tipos ni rango se seleccionan todos
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenFilter {
+    pub types: Vec<TokenType>,
+    pub lines: Option<RangeInclusive<usize>>,
+}
+
+impl TokenFilter {
+    pub fn matches(&self, token: &Token) -> bool {
+        (self.types.is_empty() || self.types.contains(&token.token_type))
+            && self.lines.as_ref().is_none_or(|lines| lines.contains(&token.row))
+    }
+
+    pub fn apply<'t, 'a>(&self, tokens: &'t [Token<'a>]) -> Vec<&'t Token<'a>> {
+        tokens.iter().filter(|token| self.matches(token)).collect()
+    }
+}
+
+// Tipos de token separados por comas (`Identifier,Operator`)
+pub fn parse_types(list: &str) -> Result<Vec<TokenType>, String> {
+    list.split(',').map(|name| name.trim().parse()).collect()
+}
+
+// Rango de líneas `desde-hasta` o una sola línea
+pub fn parse_lines(range: &str) -> Result<RangeInclusive<usize>, String> {
+    let invalid = || format!("Invalid line range: {}", range);
+    let (from, to) = range.split_once('-').unwrap_or((range, range));
+    let from: usize = from.trim().parse().map_err(|_| invalid())?;
+    let to: usize = to.trim().parse().map_err(|_| invalid())?;
+    if from > to {
+        return Err(invalid());
+    }
+    Ok(from..=to)
+}
+
+pub fn escape_lexeme(lexeme: &str) -> String {
+    let mut escaped = String::with_capacity(lexeme.len());
+    for ch in lexeme.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("\\'"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn parse_line(line: &str, version: u32) -> Result<Token<'static>, String> {
+    let rest = line
+        .trim()
+        .strip_prefix('{')
+        .ok_or("expected '{'")?;
+    let (type_str, rest) = rest.split_once(',').ok_or("expected ',' after token type")?;
+    let token_type: TokenType = type_str.trim().parse()?;
+
+    let (lexeme, rest) = parse_quoted(rest)?;
+
+    let rest = rest
+        .trim()
+        .strip_prefix(',')
+        .ok_or("expected ',' after lexeme")?;
+    let rest = rest
+        .trim_end()
+        .strip_suffix('}')
+        .ok_or("expected '}'")?;
+    // Desde la versión 3, el texto original (opcional) va después de los números
+    let (rest, source_text) = match rest.find('\'') {
+        Some(quote) if version >= 3 => {
+            let numbers = rest[..quote]
+                .trim_end()
+                .strip_suffix(',')
+                .ok_or("expected ',' before source text")?;
+            let (text, after) = parse_quoted(&rest[quote..])?;
+            if !after.trim().is_empty() {
+                return Err("unexpected text after source text".to_string());
+            }
+            (numbers, Some(text))
+        }
+        _ => (rest, None),
+    };
+    let numbers = parse_numbers(rest)?;
+
+    let token = match (version, numbers.as_slice()) {
+        (1, &[row, col]) => Token::new(token_type, lexeme, row, col),
+        (_, &[row, col, start, end]) => {
+            Token::new(token_type, lexeme, row, col).with_span(Span::new(start, end))
+        }
+        _ => return Err("expected row, column and span".to_string()),
+    };
+    match source_text {
+        Some(text) => Ok(token.with_source_text(text)),
+        None => Ok(token),
+    }
+}
+
+// Texto escapado entre comillas simples al inicio de `text`; devuelve el texto
+// y lo que sigue a la comilla de cierre
+fn parse_quoted(text: &str) -> Result<(String, &str), String> {
+    let rest = text
+        .trim_start()
+        .strip_prefix('\'')
+        .ok_or("expected opening quote")?;
+
+    let mut unescaped = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\'' => return Ok((unescaped, &rest[i + 1..])),
+            '\\' => {
+                let escaped = match chars.next() {
+                    Some((_, '\\')) => '\\',
+                    Some((_, '\'')) => '\'',
+                    Some((_, 'n')) => '\n',
+                    Some((_, 'r')) => '\r',
+                    Some((_, 't')) => '\t',
+                    Some((_, 'u')) => parse_unicode_escape(&mut chars)?,
+                    Some((_, other)) => return Err(format!("unknown escape '\\{}'", other)),
+                    None => return Err("unterminated escape".to_string()),
+                };
+                unescaped.push(escaped);
+            }
+            c => unescaped.push(c),
+        }
+    }
+    Err("unterminated lexeme".to_string())
+}
+
+fn parse_unicode_escape(chars: &mut std::str::CharIndices) -> Result<char, String> {
+    if !matches!(chars.next(), Some((_, '{'))) {
+        return Err("expected '{' in unicode escape".to_string());
+    }
+    let mut hex = String::new();
+    for (_, ch) in chars.by_ref() {
+        if ch == '}' {
+            return u32::from_str_radix(&hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or(format!("invalid unicode escape '{}'", hex));
+        }
+        hex.push(ch);
+    }
+    Err("unterminated unicode escape".to_string())
+}
+
+// Formato original: {Tipo, 'lexema', fila, columna} sin escapes. La fila y la
+// columna se toman desde el final para tolerar lexemas con comas.
+fn parse_legacy_line(line: &str) -> Result<Token<'static>, String> {
+    let rest = line
+        .trim()
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+        .ok_or("expected '{...}'")?;
+    let (type_str, rest) = rest.split_once(',').ok_or("expected ',' after token type")?;
+    let token_type: TokenType = type_str.trim().parse()?;
+
+    let mut parts = rest.rsplitn(3, ',');
+    let col = parts.next().ok_or("missing column")?;
+    let row = parts.next().ok_or("missing row")?;
+    let lexeme = parts.next().ok_or("missing lexeme")?.trim();
+    let lexeme = lexeme
+        .strip_prefix('\'')
+        .and_then(|lexeme| lexeme.strip_suffix('\''))
+        .unwrap_or(lexeme);
+    match parse_numbers(&format!("{},{}", row, col))?.as_slice() {
+        &[row, col] => Ok(Token::new(token_type, lexeme.to_string(), row, col)),
+        _ => Err("expected row and column".to_string()),
+    }
+}
+
+fn parse_numbers(text: &str) -> Result<Vec<usize>, String> {
+    text.split(',')
+        .map(|number| {
+            number
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid number '{}'", number.trim()))
+        })
+        .collect()
+}
@@ -0,0 +1,174 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Formato CSV (RFC 4180): encabezado `type,lexeme,row,col,start,end,source`
+// (la versión 2 no tenía `source` y la versión 1 tampoco `start,end`) y un
+// token por registro. `source` es el texto original del token y queda vacío
+// si coincide con el lexema. Los campos con comas, comillas o saltos de línea
+// van entre comillas dobles, duplicando las comillas internas; un campo vacío
+// entre comillas (`""`) es un texto vacío, no la ausencia de texto.
+
+use std::io::{self, Write};
+
+use crate::source_map::Span;
+use crate::token::Token;
+
+use super::TokenFileError;
+
+pub const HEADER: &str = "type,lexeme,row,col,start,end,source";
+pub const HEADER_V2: &str = "type,lexeme,row,col,start,end";
+pub const HEADER_V1: &str = "type,lexeme,row,col";
+
+pub fn write_tokens<W: Write>(out: &mut W, tokens: &[Token]) -> io::Result<()> {
+    writeln!(out, "{}", HEADER)?;
+    for token in tokens {
+        writeln!(
+            out,
+            "{:?},{},{},{},{},{},{}",
+            token.token_type,
+            quote(&token.lexeme),
+            token.row,
+            token.col,
+            token.span.start,
+            token.span.end,
+            token.source_text().map(quote).unwrap_or_default()
+        )?;
+    }
+    Ok(())
+}
+
+pub fn read_tokens(content: &str) -> Result<Vec<Token<'static>>, TokenFileError> {
+    let mut records = read_records(content)?.into_iter();
+
+    let header = records.next().map(|(_, header)| {
+        header.into_iter().map(|field| field.text).collect::<Vec<_>>().join(",")
+    });
+    let fields = match header.as_deref() {
+        Some(HEADER) => 7,
+        Some(HEADER_V2) => 6,
+        Some(HEADER_V1) => 4,
+        _ => {
+            return Err(TokenFileError::Malformed {
+                line: 1,
+                message: format!("expected header '{}'", HEADER),
+            })
+        }
+    };
+
+    records
+        .map(|(line, record)| {
+            if record.len() != fields {
+                return Err(TokenFileError::Malformed {
+                    line,
+                    message: format!("expected {} fields, found {}", fields, record.len()),
+                });
+            }
+            read_token(&record).map_err(|message| TokenFileError::Malformed { line, message })
+        })
+        .collect()
+}
+
+fn read_token(fields: &[Field]) -> Result<Token<'static>, String> {
+    let token_type = fields[0].text.parse()?;
+    let (numbers, source) = match fields.len() {
+        7 => (&fields[2..6], Some(&fields[6])),
+        _ => (&fields[2..], None),
+    };
+    let numbers = numbers
+        .iter()
+        .map(|number| {
+            number
+                .text
+                .parse()
+                .map_err(|_| format!("invalid number '{}'", number.text))
+        })
+        .collect::<Result<Vec<usize>, String>>()?;
+    let token = Token::new(token_type, fields[1].text.clone(), numbers[0], numbers[1]);
+    let token = match numbers[..] {
+        [_, _, start, end] => token.with_span(Span::new(start, end)),
+        _ => token,
+    };
+    match source {
+        Some(source) if source.quoted || !source.text.is_empty() => {
+            Ok(token.with_source_text(source.text.clone()))
+        }
+        _ => Ok(token),
+    }
+}
+
+fn quote(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) || text.is_empty() {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+struct Field {
+    text: String,
+    // Un campo vacío entre comillas es un texto vacío; sin comillas, un valor ausente
+    quoted: bool,
+}
+
+// Separa el contenido en registros, devolviendo la línea donde empieza cada uno
+fn read_records(content: &str) -> Result<Vec<(usize, Vec<Field>)>, TokenFileError> {
+    let mut records = Vec::new();
+    let mut chars = content.chars().peekable();
+    let mut line = 1;
+
+    while chars.peek().is_some() {
+        let start_line = line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        loop {
+            match chars.next() {
+                Some('"') if field.is_empty() => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            Some('"') if chars.peek() == Some(&'"') => {
+                                chars.next();
+                                field.push('"');
+                            }
+                            Some('"') => break,
+                            Some(c) => {
+                                if c == '\n' {
+                                    line += 1;
+                                }
+                                field.push(c);
+                            }
+                            None => {
+                                return Err(TokenFileError::Malformed {
+                                    line: start_line,
+                                    message: "unterminated quoted field".to_string(),
+                                })
+                            }
+                        }
+                    }
+                },
+                Some(',') => fields.push(Field {
+                    text: std::mem::take(&mut field),
+                    quoted: std::mem::take(&mut quoted),
+                }),
+                Some('\r') if chars.peek() == Some(&'\n') => {}
+                Some('\n') | None => {
+                    line += 1;
+                    fields.push(Field {
+                        text: std::mem::take(&mut field),
+                        quoted,
+                    });
+                    break;
+                }
+                Some(c) => field.push(c),
+            }
+        }
+        if fields.len() > 1 || !fields[0].text.is_empty() || fields[0].quoted {
+            records.push((start_line, fields));
+        }
+    }
+
+    Ok(records)
+}
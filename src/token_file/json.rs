@@ -0,0 +1,334 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Formato JSON:
+//
+//   {"version": 3, "tokens": [
+//     {"type": "Let", "lexeme": "let", "row": 1, "col": 1, "start": 0, "end": 3},
+//     {"type": "Identifier", "lexeme": "año", "row": 1, "col": 5, "start": 4, "end": 8, "source": "Año"},
+//     ...
+//   ]}
+//
+// `source` (el texto original si difiere del lexema) solo existe desde la
+// versión 3 y el span desde la versión 2.
+
+use std::io::{self, Write};
+
+use crate::source_map::Span;
+use crate::token::Token;
+
+use super::{TokenFileError, VERSION};
+
+pub fn write_tokens<W: Write>(out: &mut W, tokens: &[Token]) -> io::Result<()> {
+    writeln!(out, "{{\"version\": {}, \"tokens\": [", VERSION)?;
+    for (i, token) in tokens.iter().enumerate() {
+        let separator = if i + 1 < tokens.len() { "," } else { "" };
+        let source = match token.source_text() {
+            Some(text) => format!(", \"source\": {}", quote(text)),
+            None => String::new(),
+        };
+        writeln!(
+            out,
+            "  {{\"type\": \"{:?}\", \"lexeme\": {}, \"row\": {}, \"col\": {}, \"start\": {}, \"end\": {}{}}}{}",
+            token.token_type,
+            quote(&token.lexeme),
+            token.row,
+            token.col,
+            token.span.start,
+            token.span.end,
+            source,
+            separator
+        )?;
+    }
+    writeln!(out, "]}}")
+}
+
+pub fn read_tokens(content: &str) -> Result<Vec<Token<'static>>, TokenFileError> {
+    let mut parser = JsonParser { content, pos: 0 };
+    let value = parser.parse_value().and_then(|value| {
+        parser.skip_whitespace();
+        if parser.pos < content.len() {
+            Err(parser.error("unexpected trailing characters"))
+        } else {
+            Ok(value)
+        }
+    })?;
+
+    let tokens = match value {
+        Json::Object(fields) => {
+            match field(&fields, "version") {
+                Some(Json::Number(version)) if (1..=VERSION as u64).contains(version) => {}
+                Some(Json::Number(version)) => {
+                    return Err(TokenFileError::UnsupportedVersion(version.to_string()))
+                }
+                _ => return Err(malformed(1, "missing \"version\"")),
+            }
+            match field(&fields, "tokens") {
+                Some(Json::Array(tokens)) => tokens.clone(),
+                _ => return Err(malformed(1, "missing \"tokens\" array")),
+            }
+        }
+        Json::Array(tokens) => tokens,
+        _ => return Err(malformed(1, "expected an object or an array")),
+    };
+
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| read_token(token).map_err(|message| malformed(i + 1, &message)))
+        .collect()
+}
+
+fn read_token(value: &Json) -> Result<Token<'static>, String> {
+    let fields = match value {
+        Json::Object(fields) => fields,
+        _ => return Err("expected a token object".to_string()),
+    };
+    let token_type = match field(fields, "type") {
+        Some(Json::String(token_type)) => token_type.parse()?,
+        _ => return Err("missing \"type\"".to_string()),
+    };
+    let lexeme = match field(fields, "lexeme") {
+        Some(Json::String(lexeme)) => lexeme.clone(),
+        _ => return Err("missing \"lexeme\"".to_string()),
+    };
+    let row = match field(fields, "row") {
+        Some(Json::Number(row)) => *row as usize,
+        _ => return Err("missing \"row\"".to_string()),
+    };
+    let col = match field(fields, "col") {
+        Some(Json::Number(col)) => *col as usize,
+        _ => return Err("missing \"col\"".to_string()),
+    };
+    // El span es opcional (no existe en la versión 1)
+    let token = Token::new(token_type, lexeme, row, col);
+    let token = match (field(fields, "start"), field(fields, "end")) {
+        (Some(Json::Number(start)), Some(Json::Number(end))) => {
+            token.with_span(Span::new(*start as usize, *end as usize))
+        }
+        (None, None) => token,
+        _ => return Err("invalid \"start\"/\"end\"".to_string()),
+    };
+    match field(fields, "source") {
+        Some(Json::String(text)) => Ok(token.with_source_text(text.clone())),
+        None => Ok(token),
+        _ => Err("invalid \"source\"".to_string()),
+    }
+}
+
+// En JSON el error se reporta con el número de token dentro del arreglo
+fn malformed(line: usize, message: &str) -> TokenFileError {
+    TokenFileError::Malformed {
+        line,
+        message: message.to_string(),
+    }
+}
+
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => {
+                quoted.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[derive(Debug, Clone)]
+enum Json {
+    Null,
+    Bool,
+    Number(u64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+fn field<'a>(fields: &'a [(String, Json)], name: &str) -> Option<&'a Json> {
+    fields
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value)
+}
+
+// Parser mínimo de JSON: suficiente para los archivos de tokens (los números
+// solo pueden ser enteros no negativos)
+struct JsonParser<'a> {
+    content: &'a str,
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self, message: &str) -> TokenFileError {
+        let line = self.content[..self.pos].matches('\n').count() + 1;
+        malformed(line, message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.content[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TokenFileError> {
+        self.skip_whitespace();
+        if self.bump() == Some(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", expected)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, TokenFileError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => Ok(Json::String(self.parse_string()?)),
+            Some(c) if c.is_ascii_digit() => self.parse_number(),
+            Some(_) => self.parse_keyword(),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, TokenFileError> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.bump();
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.expect(':')?;
+            let value = self.parse_value()?;
+            fields.push((key, value));
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => continue,
+                Some('}') => return Ok(Json::Object(fields)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Json, TokenFileError> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.bump();
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => continue,
+                Some(']') => return Ok(Json::Array(items)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, TokenFileError> {
+        if self.bump() != Some('"') {
+            return Err(self.error("expected '\"'"));
+        }
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(text),
+                Some('\\') => {
+                    let escaped = match self.bump() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.parse_unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    text.push(escaped);
+                }
+                Some(c) => text.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, TokenFileError> {
+        let hex = self.content.get(self.pos..self.pos + 4).unwrap_or("");
+        let value = u32::from_str_radix(hex, 16).map_err(|_| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(value)
+    }
+
+    // Los caracteres fuera del plano básico vienen como pares sustitutos UTF-16
+    fn parse_unicode_escape(&mut self) -> Result<char, TokenFileError> {
+        let high = self.parse_hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.content[self.pos..].starts_with("\\u") {
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.pos += 2;
+            let low = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid \\u escape"))
+    }
+
+    fn parse_number(&mut self) -> Result<Json, TokenFileError> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.content[start..self.pos]
+            .parse()
+            .map(Json::Number)
+            .map_err(|_| self.error("invalid number"))
+    }
+
+    fn parse_keyword(&mut self) -> Result<Json, TokenFileError> {
+        let rest = &self.content[self.pos..];
+        for (keyword, value) in [("null", Json::Null), ("true", Json::Bool), ("false", Json::Bool)] {
+            if rest.starts_with(keyword) {
+                self.pos += keyword.len();
+                return Ok(value);
+            }
+        }
+        Err(self.error("unexpected character"))
+    }
+}
@@ -3,28 +3,285 @@
 // - Matias Leer
 // - Melissa Carvajal
 
-use std::fs::File;
-use std::io::{self, BufRead};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
 use std::path::Path;
 use std::env;
 
-// Función principal para imprimir tokens desde un archivo
+use compilador_rust::lexer::{self, LexerOptions};
+use compilador_rust::token::{self, Token, TokenType};
+use compilador_rust::token_diff::{self, Change};
+use compilador_rust::token_file::{self, TokenFilter};
+
+fn usage() -> String {
+    format!(
+        "Uso: tokens [archivo] [--table] [--type <Tipo>[,<Tipo>...]] [--lines <desde>[-<hasta>]] [--stats] [--longest <n>] [--check] [--source]\n       tokens --diff <anterior> <nuevo> [--ignore-positions] {}",
+        lexer::options::USAGE
+    )
+}
+
+#[derive(Default)]
+struct Options {
+    files: Vec<String>,
+    table: bool,
+    filter: TokenFilter,
+    stats: bool,
+    longest: Option<usize>,
+    check: bool,
+    source: bool,
+    diff: bool,
+    ignore_positions: bool,
+    // Para los archivos `.tri` que se lexean al vuelo
+    lexer: LexerOptions,
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn parse_args(args: &[String]) -> Options {
+    let mut options = Options::default();
+    let mut i = 1;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--table", _) => options.table = true,
+            ("--stats", _) => options.stats = true,
+            ("--check", _) => options.check = true,
+            ("--source", _) => options.source = true,
+            ("--diff", _) => options.diff = true,
+            ("--ignore-positions", _) => options.ignore_positions = true,
+            ("--type", Some(value)) => {
+                let types = token_file::parse_types(value).unwrap_or_else(|e| fail(&e));
+                options.filter.types.extend(types);
+                i += 1;
+            }
+            ("--lines", Some(value)) => {
+                options.filter.lines = Some(token_file::parse_lines(value).unwrap_or_else(|e| fail(&e)));
+                i += 1;
+            }
+            ("--longest", Some(value)) => {
+                options.longest = Some(value.parse().unwrap_or_else(|_| fail(&usage())));
+                i += 1;
+            }
+            (arg, _) => match options.lexer.parse_arg(&args[i..]) {
+                Ok(0) if arg.starts_with("--") => fail(&usage()),
+                Ok(0) => options.files.push(arg.to_string()),
+                Ok(consumed) => i += consumed - 1,
+                Err(e) => fail(&e),
+            },
+        }
+        i += 1;
+    }
+    options
+}
+
+// Los archivos `.tri` se lexean al vuelo; los demás se leen como archivos de tokens
+fn load_tokens(input_file: &str, options: &LexerOptions) -> Vec<Token<'static>> {
+    if Path::new(input_file).extension().is_some_and(|extension| extension == "tri") {
+        let source = fs::read_to_string(input_file).expect("Error abriendo el archivo");
+        options.lexer(source).collect()
+    } else {
+        read_tokens(input_file)
+    }
+}
+
+fn read_tokens(input_file: &str) -> Vec<Token<'static>> {
+    let archivo = File::open(Path::new(input_file)).expect("Error abriendo el archivo");
+    let reader = io::BufReader::new(archivo);
+    match token_file::read_tokens(reader) {
+        Ok(tokens) => tokens,
+        Err(err) => {
+            eprintln!("Error leyendo los tokens: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_list(tokens: &[&Token]) {
+    for token in tokens {
+        println!(
+            "Token {{ Tipo: {:?}, Lexema: '{}', Ln: {}, Col: {} }}",
+            token.token_type,
+            token_file::escape_lexeme(&token.lexeme),
+            token.row,
+            token.col
+        );
+    }
+}
+
+fn print_table(tokens: &[&Token]) {
+    let header = ["Tipo", "Lexema", "Ln", "Col", "Span"];
+    let rows: Vec<[String; 5]> = tokens
+        .iter()
+        .map(|token| {
+            [
+                format!("{:?}", token.token_type),
+                format!("'{}'", token_file::escape_lexeme(&token.lexeme)),
+                token.row.to_string(),
+                token.col.to_string(),
+                format!("{}..{}", token.span.start, token.span.end),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(|title| title.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header = header.map(String::from);
+    let separator = widths.map(|width| "-".repeat(width));
+    for row in [&header, &separator].into_iter().chain(&rows) {
+        let line = format!(
+            "{:<w0$}  {:<w1$}  {:>w2$}  {:>w3$}  {:<w4$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4]
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+fn print_stats(tokens: &[&Token]) {
+    let mut counts: HashMap<&TokenType, usize> = HashMap::new();
+    for token in tokens {
+        *counts.entry(&token.token_type).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts
+        .into_iter()
+        .map(|(token_type, count)| (format!("{:?}", token_type), count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let width = counts.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, count) in &counts {
+        println!("{:<width$}  {}", name, count, width = width);
+    }
+    println!("{:<width$}  {}", "Total", tokens.len(), width = width);
+}
+
+// Identificadores distintos ordenados por longitud (en caracteres)
+fn print_longest(tokens: &[&Token], n: usize) {
+    let mut identifiers: Vec<&Token> = Vec::new();
+    for token in tokens {
+        if token.token_type == TokenType::Identifier
+            && !identifiers.iter().any(|seen| seen.lexeme == token.lexeme)
+        {
+            identifiers.push(token);
+        }
+    }
+    identifiers.sort_by_key(|token| std::cmp::Reverse(token.lexeme.chars().count()));
+    for token in identifiers.into_iter().take(n) {
+        println!(
+            "{:>4}  {}  (Ln: {}, Col: {})",
+            token.lexeme.chars().count(),
+            token.lexeme,
+            token.row,
+            token.col
+        );
+    }
+}
+
+fn describe(token: &Token) -> String {
+    format!(
+        "{:?} '{}' (Ln: {}, Col: {})",
+        token.token_type,
+        token_file::escape_lexeme(&token.lexeme),
+        token.row,
+        token.col
+    )
+}
+
+fn print_diff(changes: &[Change]) {
+    for change in changes {
+        match change {
+            Change::Inserted(token) => println!("+ insertado     {}", describe(token)),
+            Change::Deleted(token) => println!("- eliminado     {}", describe(token)),
+            Change::Retyped { old, new } => println!(
+                "~ cambió tipo   '{}' {:?} -> {:?} (Ln: {}, Col: {})",
+                token_file::escape_lexeme(&new.lexeme),
+                old.token_type,
+                new.token_type,
+                new.row,
+                new.col
+            ),
+            Change::Moved { old, new } => println!(
+                "> movido        {:?} '{}' {}:{} -> {}:{}",
+                new.token_type,
+                token_file::escape_lexeme(&new.lexeme),
+                old.row,
+                old.col,
+                new.row,
+                new.col
+            ),
+        }
+    }
+}
+
+// Función principal para imprimir tokens desde un archivo (texto, JSON o CSV)
 fn main() {
     let args: Vec<String> = env::args().collect();
-    let archivo_entrada = if args.len() > 1 {
-        &args[1]
-    } else {
-        "tokens.out"
+    let options = parse_args(&args);
+
+    // Como `diff`, termina con código 1 si hay diferencias
+    if options.diff {
+        let [old_file, new_file] = options.files.as_slice() else {
+            fail(&usage());
+        };
+        let old = load_tokens(old_file, &options.lexer);
+        let new = load_tokens(new_file, &options.lexer);
+        let changes = token_diff::diff(&old, &new, options.ignore_positions);
+        print_diff(&changes);
+        if !changes.is_empty() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let archivo_entrada = match options.files.as_slice() {
+        [] => "tokens.out",
+        [archivo] => archivo.as_str(),
+        _ => fail(&usage()),
     };
 
-    let ruta_entrada = Path::new(archivo_entrada);
-    let archivo = File::open(ruta_entrada).expect("Error abriendo el archivo");
-    let reader = io::BufReader::new(archivo);
+    let tokens = read_tokens(archivo_entrada);
 
-    for line in reader.lines() {
-        match line {
-            Ok(token) => println!("{}", token),
-            Err(err) => eprintln!("Error leyendo el token: {}", err),
+    if options.check {
+        let problems = token_file::check(&tokens);
+        for problem in &problems {
+            eprintln!("{}: {}", archivo_entrada, problem);
         }
+        if !problems.is_empty() {
+            std::process::exit(1);
+        }
+        println!("{}: {} tokens, correcto", archivo_entrada, tokens.len());
+        return;
+    }
+
+    let selected = options.filter.apply(&tokens);
+
+    if options.source {
+        let selected: Vec<Token> = selected.into_iter().cloned().collect();
+        print!("{}", token::detokenize(&selected));
+    } else if options.stats {
+        print_stats(&selected);
+    } else if let Some(n) = options.longest {
+        print_longest(&selected, n);
+    } else if options.table {
+        print_table(&selected);
+    } else {
+        print_list(&selected);
     }
 }
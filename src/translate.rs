@@ -0,0 +1,95 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Traduce un programa entre dialectos de palabras reservadas conservando
+// comentarios, espacios y el resto de los tokens tal como están.
+
+use std::env;
+use std::fs;
+
+use compilador_rust::keywords::{self, Dialect};
+use compilador_rust::lexer::{options, Lexer, LexerOptions};
+
+fn parse_dialect(arg: &str) -> Dialect {
+    arg.parse().unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let usage = format!(
+        "Uso: {} <archivo_entrada> [--from en|es] [--to en|es] [-o <archivo_salida>] {}",
+        args[0],
+        options::USAGE
+    );
+
+    let mut input_file: Option<&str> = None;
+    let mut output_file: Option<&str> = None;
+    // `--from` es el dialecto del lexer (`--dialect`), que aquí es español por defecto
+    let mut options = LexerOptions { dialect: Dialect::Spanish, ..LexerOptions::default() };
+    let mut to = Dialect::English;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-o" if i + 1 < args.len() => {
+                output_file = Some(&args[i + 1]);
+                i += 1;
+            }
+            "--from" if i + 1 < args.len() => {
+                options.dialect = parse_dialect(&args[i + 1]);
+                i += 1;
+            }
+            "--to" if i + 1 < args.len() => {
+                to = parse_dialect(&args[i + 1]);
+                i += 1;
+            }
+            arg => match options.parse_arg(&args[i..]) {
+                Ok(0) => input_file = Some(arg),
+                Ok(consumed) => i += consumed - 1,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            },
+        }
+        i += 1;
+    }
+
+    let input_file = match input_file {
+        Some(input_file) => input_file,
+        None => {
+            eprintln!("{}", usage);
+            std::process::exit(1);
+        }
+    };
+
+    let source = fs::read_to_string(input_file).unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    });
+    let mut lexer = options.configure(Lexer::new(source.as_str()).with_trivia());
+    let tokens: Vec<_> = lexer.by_ref().collect();
+    for error in lexer.errors() {
+        eprintln!("{}", error);
+    }
+
+    let translated = match keywords::translate(&tokens, &to.keywords()) {
+        Ok(translated) => translated,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        }
+    };
+    match output_file {
+        Some(output_file) => fs::write(output_file, translated).unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        }),
+        None => print!("{}", translated),
+    }
+}
@@ -0,0 +1,61 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// Tamaño de palabra de la máquina destino. Determina `maxint`, el mayor
+// literal entero que acepta el lexer; el parser (y las fases posteriores)
+// usan el mismo valor.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WordSize {
+    // TAM clásico: enteros de 16 bits, maxint = 32767
+    Bits16,
+    #[default]
+    Bits64,
+}
+
+impl WordSize {
+    pub fn maxint(&self) -> i64 {
+        match self {
+            WordSize::Bits16 => i16::MAX as i64,
+            WordSize::Bits64 => i64::MAX,
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        match self {
+            WordSize::Bits16 => 16,
+            WordSize::Bits64 => 64,
+        }
+    }
+
+    // Valor de un literal entero, o `None` si excede `maxint`
+    pub fn parse_literal(&self, lexeme: &str) -> Option<i64> {
+        lexeme
+            .parse::<i64>()
+            .ok()
+            .filter(|value| *value <= self.maxint())
+    }
+}
+
+impl FromStr for WordSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "16" => Ok(WordSize::Bits16),
+            "64" => Ok(WordSize::Bits64),
+            _ => Err(format!("Unknown word size: {} (expected 16 or 64)", s)),
+        }
+    }
+}
+
+impl fmt::Display for WordSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
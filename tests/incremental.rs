@@ -0,0 +1,217 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+use compilador_rust::incremental::{relex, relex_with, Relexed, TextEdit};
+use compilador_rust::lexer::{LexError, Lexer};
+use compilador_rust::token::{Token, TokenType};
+
+const PROGRAM: &str = "let
+  var total : Integer;
+  var c : Char
+in
+  begin
+    total := 0;
+    c := 'a';
+    putint(total)
+  end";
+
+// Lexer con trivia y comentarios de bloque, para que la comparación cubra también los espacios
+fn configure(lexer: Lexer<'_>) -> Lexer<'_> {
+    lexer.with_trivia().with_block_comments()
+}
+
+fn lex(source: &str) -> Vec<Token<'_>> {
+    configure(Lexer::new(source)).collect()
+}
+
+// Offset en bytes de la `n`-ésima aparición (base 0) de `text` en `source`
+fn find(source: &str, text: &str, n: usize) -> usize {
+    source.match_indices(text).nth(n).map(|(i, _)| i).unwrap()
+}
+
+// Re-analiza `source` después de la edición y verifica que el resultado sea
+// igual a lexear de nuevo todo el texto editado
+fn check<'a>(source: &str, edit: &TextEdit, edited: &'a str) -> Relexed<'a> {
+    assert_eq!(edit.apply(source), edited);
+    let old = lex(source);
+    let relexed = relex_with(&old, edited, edit, configure);
+    assert_eq!(relexed.tokens, lex(edited));
+
+    // Fuera de los rangos cambiados, los tokens son los viejos (desplazados)
+    let (changed, replaced) = (&relexed.changed, &relexed.replaced);
+    assert_eq!(changed.start, replaced.start);
+    assert_eq!(
+        relexed.tokens.len() - changed.end,
+        old.len() - replaced.end,
+        "tail lengths differ"
+    );
+    for (new, old) in relexed.tokens[changed.end..].iter().zip(&old[replaced.end..]) {
+        assert_eq!((&new.token_type, &new.lexeme), (&old.token_type, &old.lexeme));
+    }
+    relexed
+}
+
+fn types(tokens: &[Token]) -> Vec<TokenType> {
+    tokens.iter().map(|token| token.token_type.clone()).collect()
+}
+
+#[test]
+fn renaming_an_identifier_only_replaces_that_line() {
+    let start = find(PROGRAM, "total", 1);
+    let edit = TextEdit::new(start..start + 5, "sum");
+    let edited = edit.apply(PROGRAM);
+    let relexed = check(PROGRAM, &edit, &edited);
+
+    // Se re-analiza desde el inicio de la línea (`total := 0`) hasta el primer token igual
+    let old = lex(PROGRAM);
+    let line = old.iter().position(|token| token.span.start == start).unwrap();
+    assert_eq!(relexed.replaced, line..line + 1);
+    assert_eq!(relexed.changed, line..line + 1);
+    assert_eq!(relexed.tokens[line].lexeme, "sum");
+    assert!(relexed.errors.is_empty());
+
+    // Los tokens posteriores se desplazan: 2 bytes menos en la misma fila
+    let assign = &relexed.tokens[line + 1];
+    assert_eq!(assign.token_type, TokenType::Assign);
+    assert_eq!(assign.span.start, old[line + 1].span.start - 2);
+    assert_eq!((assign.row, assign.col), (old[line + 1].row, old[line + 1].col - 2));
+    let last = relexed.tokens.last().unwrap();
+    assert_eq!(last.span.start, old.last().unwrap().span.start - 2);
+    assert_eq!(last.row, old.last().unwrap().row);
+}
+
+#[test]
+fn deleting_a_space_joins_colon_and_equals() {
+    let source = "let var x : Integer in x : = 1";
+    let start = find(source, ": =", 0) + 1;
+    let edit = TextEdit::new(start..start + 1, "");
+    let edited = edit.apply(source);
+    let relexed = check(source, &edit, &edited);
+
+    let changed = &relexed.tokens[relexed.changed.clone()];
+    assert!(types(changed).contains(&TokenType::Assign));
+    let replaced = &lex(source)[relexed.replaced.clone()];
+    assert!(types(replaced).contains(&TokenType::Colon));
+    assert!(types(replaced).contains(&TokenType::Equals));
+
+    // Y al revés: insertar un espacio separa `:=`
+    let start = find(&edited, ":=", 0) + 1;
+    let edit = TextEdit::new(start..start, " ");
+    let split = edit.apply(&edited);
+    check(&edited, &edit, &split);
+}
+
+#[test]
+fn opening_a_block_comment_swallows_the_following_lines() {
+    let start = find(PROGRAM, "    total := 0", 0);
+    let edit = TextEdit::new(start..start, "{! ");
+    let edited = edit.apply(PROGRAM);
+    let relexed = check(PROGRAM, &edit, &edited);
+
+    // El comentario no se cierra: desaparecen todos los tokens hasta el EOF,
+    // que se vuelve a sincronizar (su trivia ahora incluye el comentario)
+    let old = lex(PROGRAM);
+    assert!(relexed.changed.is_empty());
+    assert_eq!(relexed.replaced, relexed.changed.start..old.len() - 1);
+    assert_eq!(relexed.tokens.len(), relexed.changed.start + 1);
+    assert!(matches!(relexed.errors[..], [LexError::UnterminatedComment { row: 6, .. }]));
+
+    // Al cerrarlo, los tokens posteriores al comentario se vuelven a sincronizar
+    let end = find(&edited, "c := 'a';", 0) + "c := 'a';".len();
+    let edit = TextEdit::new(end..end, " !}");
+    let closed = edit.apply(&edited);
+    let relexed = check(&edited, &edit, &closed);
+    assert!(relexed.errors.is_empty());
+    assert_eq!(relexed.tokens.iter().filter(|token| token.lexeme == "total").count(), 2);
+
+    // Quitar el `{!` hace reaparecer los tokens (el `!}` que queda es un comentario de línea)
+    let start = find(&closed, "{! ", 0);
+    let edit = TextEdit::new(start..start + 3, "");
+    let reopened = edit.apply(&closed);
+    let relexed = check(&closed, &edit, &reopened);
+    assert!(relexed.errors.is_empty());
+    assert_eq!(types(&relexed.tokens), types(&old));
+}
+
+#[test]
+fn edits_inside_char_literals() {
+    // 'a' pasa a ser un escape
+    let start = find(PROGRAM, "'a'", 0) + 1;
+    let edit = TextEdit::new(start..start + 1, "\\n");
+    let edited = edit.apply(PROGRAM);
+    let relexed = check(PROGRAM, &edit, &edited);
+    let literal = &relexed.tokens[relexed.changed.clone()];
+    assert!(literal.iter().any(|token| token.lexeme == "\n" && token.text() == "'\\n'"));
+
+    // Borrar la comilla de cierre deja el literal sin cerrar
+    let start = find(PROGRAM, "'a'", 0) + 2;
+    let edit = TextEdit::new(start..start + 1, "");
+    let edited = edit.apply(PROGRAM);
+    let relexed = check(PROGRAM, &edit, &edited);
+    assert!(matches!(relexed.errors[..], [LexError::UnterminatedCharLiteral { row: 7, col: 10 }]));
+
+    // Agregar una comilla en otra parte de la línea cambia cómo se lee el literal
+    let start = find(&edited, "'a;", 0) + 2;
+    let edit = TextEdit::new(start..start, "'");
+    let fixed = edit.apply(&edited);
+    let relexed = check(&edited, &edit, &fixed);
+    assert!(relexed.errors.is_empty());
+}
+
+#[test]
+fn edits_at_the_end_of_the_file() {
+    // Agregar al final
+    let end = PROGRAM.len();
+    let edit = TextEdit::new(end..end, ";\nputint(c)");
+    let appended = edit.apply(PROGRAM);
+    let relexed = check(PROGRAM, &edit, &appended);
+    assert_eq!(relexed.tokens.last().unwrap().token_type, TokenType::EOF);
+
+    // Borrar hasta el final
+    let start = find(PROGRAM, "    putint", 0);
+    let edit = TextEdit::new(start..PROGRAM.len(), "");
+    let truncated = edit.apply(PROGRAM);
+    let relexed = check(PROGRAM, &edit, &truncated);
+    assert_eq!(relexed.replaced.end, lex(PROGRAM).len() - 1);
+    assert_eq!(relexed.tokens.len(), relexed.changed.end + 1);
+
+    // Agregar un comentario sin salto de línea al final
+    let end = PROGRAM.len();
+    let edit = TextEdit::new(end..end, " ! fin");
+    let commented = edit.apply(PROGRAM);
+    check(PROGRAM, &edit, &commented);
+
+    // Vaciar el archivo
+    let edit = TextEdit::new(0..PROGRAM.len(), "");
+    let relexed = check(PROGRAM, &edit, "");
+    assert_eq!(types(&relexed.tokens), [TokenType::EOF]);
+}
+
+#[test]
+fn relex_without_trivia_matches_a_full_lex() {
+    let start = find(PROGRAM, "Char", 0);
+    let edit = TextEdit::new(start..start + 4, "Integer;\n  var d : Char");
+    let edited = edit.apply(PROGRAM);
+    let old: Vec<_> = Lexer::new(PROGRAM).collect();
+    let relexed = relex(&old, &edited, &edit);
+    assert_eq!(relexed.tokens, Lexer::new(edited.as_str()).collect::<Vec<_>>());
+    assert_eq!(relexed.tokens.len() - old.len(), 5);
+}
+
+#[test]
+fn every_single_character_edit_matches_a_full_lex() {
+    let source = "let var c : Char {! a {! b !} !} in c := '\\''; ! x\nif c = ''' then x:=1 else y :=2";
+    let characters = ["", " ", "\n", "'", ":", "=", "{", "!", "}", "\\", "x"];
+    let boundaries: Vec<usize> = (0..=source.len()).filter(|&i| source.is_char_boundary(i)).collect();
+    for window in boundaries.windows(2) {
+        for replacement in characters {
+            for range in [window[0]..window[0], window[0]..window[1]] {
+                let edit = TextEdit::new(range, replacement);
+                let edited = edit.apply(source);
+                check(source, &edit, &edited);
+            }
+        }
+    }
+}
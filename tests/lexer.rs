@@ -0,0 +1,343 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+use compilador_rust::keywords::{self, Dialect, KeywordTable};
+use compilador_rust::lexer::{LexError, Lexer, LexerOptions};
+use compilador_rust::source_map::SourceMap;
+use compilador_rust::token::{self, OperatorKind, TokenType, TriviaKind};
+use compilador_rust::word_size::WordSize;
+
+const PROGRAM: &str = "! encabezado\n\
+let\n\
+  var c : Char {! comentario {! anidado !} de bloque !};\n\
+  const q ~ ''''; ! comilla\r\n\
+  const nl ~ '\\n'\n\
+in\n\
+  begin c := '\\t'; put(q) end\n\
+  ! al final, sin salto de línea";
+
+#[test]
+fn to_source_rebuilds_the_program_byte_for_byte() {
+    let tokens: Vec<_> = Lexer::new(PROGRAM).with_trivia().with_block_comments().collect();
+
+    assert_eq!(token::to_source(&tokens), PROGRAM);
+
+    // También con lexemas propios (`String`)
+    let owned: Vec<_> = Lexer::new(PROGRAM.to_string())
+        .with_trivia()
+        .with_block_comments()
+        .collect();
+    assert_eq!(token::to_source(&owned), PROGRAM);
+}
+
+#[test]
+fn trivia_and_source_text_are_kept_on_tokens() {
+    let tokens: Vec<_> = Lexer::new(PROGRAM).with_trivia().with_block_comments().collect();
+
+    let semicolon = tokens
+        .iter()
+        .find(|token| token.token_type == TokenType::Semicolon)
+        .unwrap();
+    let comments: Vec<_> = semicolon
+        .leading_trivia()
+        .iter()
+        .map(|trivia| (trivia.kind, trivia.text.as_ref()))
+        .collect();
+    assert_eq!(
+        comments,
+        vec![
+            (TriviaKind::Whitespace, " "),
+            (TriviaKind::Comment, "{! comentario {! anidado !} de bloque !}"),
+        ]
+    );
+
+    let chars: Vec<_> = tokens
+        .iter()
+        .filter(|token| token.token_type == TokenType::CharLiteral)
+        .map(|token| (token.lexeme.as_ref(), token.text()))
+        .collect();
+    assert_eq!(chars, vec![("'", "''''"), ("\n", "'\\n'"), ("\t", "'\\t'")]);
+
+    let eof = tokens.last().unwrap();
+    assert_eq!(eof.token_type, TokenType::EOF);
+    assert_eq!(
+        eof.leading_trivia().last().map(|trivia| trivia.text.as_ref()),
+        Some("! al final, sin salto de línea")
+    );
+}
+
+#[test]
+fn tokens_carry_no_trivia_by_default() {
+    for token in Lexer::new(PROGRAM).with_block_comments() {
+        assert!(token.leading_trivia().is_empty());
+        assert_eq!(token.source_text(), None);
+    }
+}
+
+#[test]
+fn lexer_options_are_read_from_command_line_arguments() {
+    let args: Vec<String> = [
+        "--dialect", "es", "--block-comments", "x.tri", "--word-size", "16", "--nfc", "--ignore-case",
+    ]
+    .iter()
+    .map(|arg| arg.to_string())
+    .collect();
+    let mut options = LexerOptions::default();
+    let mut files = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match options.parse_arg(&args[i..]).unwrap() {
+            0 => {
+                files.push(args[i].as_str());
+                i += 1;
+            }
+            consumed => i += consumed,
+        }
+    }
+
+    assert_eq!(files, ["x.tri"]);
+    assert_eq!(options.dialect, Dialect::Spanish);
+    assert_eq!(options.word_size, WordSize::Bits16);
+    assert!(options.block_comments && options.normalize_identifiers && options.ignore_case);
+
+    let types: Vec<_> = options
+        .lexer("SEA {! nota !} var x : Integer EN x := 40000")
+        .map(|token| token.token_type)
+        .collect();
+    assert_eq!(types[0], TokenType::Let);
+    assert_eq!(types[1], TokenType::Var);
+    assert_eq!(types[5], TokenType::In);
+
+    let mut lexer = options.lexer("40000");
+    lexer.by_ref().for_each(drop);
+    assert_eq!(lexer.errors().len(), 1);
+
+    assert!(options.parse_arg(&["--dialect".to_string()]).is_err());
+    assert!(options.parse_arg(&["--word-size".to_string(), "32".to_string()]).is_err());
+}
+
+#[test]
+fn english_keyword_table_agrees_with_its_words() {
+    let table = KeywordTable::english();
+    for (word, token_type) in table.iter() {
+        assert_eq!(table.lookup(word), Some(token_type.clone()), "{}", word);
+    }
+    assert_eq!(table.lookup("Begin"), None);
+    assert_eq!(table.lookup("beginx"), None);
+
+    // Al agregar palabras la tabla deja de consultarse con el `match`
+    let mut table = KeywordTable::english();
+    table.insert("hasta", TokenType::Do);
+    assert_eq!(table.lookup("hasta"), Some(TokenType::Do));
+    assert_eq!(table.lookup("while"), Some(TokenType::While));
+}
+
+#[test]
+fn backslash_literal_closes_at_the_escaped_quote() {
+    let mut lexer = Lexer::new("c := '\\'; d := '\\''; e := '\\\\'");
+    let literals: Vec<_> = lexer
+        .by_ref()
+        .filter(|token| token.token_type == TokenType::CharLiteral)
+        .map(|token| (token.lexeme.into_owned(), token.row, token.col))
+        .collect();
+    assert_eq!(
+        literals,
+        [("\\".to_string(), 1, 6), ("'".to_string(), 1, 16), ("\\".to_string(), 1, 27)]
+    );
+    assert!(lexer.errors().is_empty());
+
+    let mut lexer = Lexer::new("c := '';\nd := 'ab'; e := 1");
+    let types: Vec<_> = lexer.by_ref().map(|token| token.token_type).collect();
+    assert_eq!(types[2], TokenType::Illegal);
+    assert_eq!(types[6], TokenType::Illegal);
+    assert_eq!(types[7..10], [TokenType::Semicolon, TokenType::Identifier, TokenType::Assign]);
+    assert_eq!(
+        lexer.errors(),
+        [LexError::EmptyCharLiteral { row: 1, col: 6 }, LexError::MultiCharLiteral { row: 2, col: 6 }]
+    );
+}
+
+#[test]
+fn spanish_dialect_produces_the_same_token_types() {
+    let types = |lexer: Lexer| lexer.map(|token| token.token_type).collect::<Vec<_>>();
+    let english = Lexer::new("let var x : Integer in while x < 3 do if x = 0 then x := 1 else x := x + 1");
+    let spanish = Lexer::new("sea var x : Integer en mientras x < 3 haga si x = 0 entonces x := 1 sino x := x + 1")
+        .with_keywords(Dialect::Spanish.keywords());
+    assert_eq!(types(spanish), types(english));
+}
+
+#[test]
+fn translate_rewrites_keywords_and_keeps_trivia() {
+    let source = "SEA {! nota !} var x : Integer\nEN ! fin\n  mientras x < 3 haga x := x + 1\n";
+    let options = LexerOptions {
+        dialect: Dialect::Spanish,
+        block_comments: true,
+        ignore_case: true,
+        ..LexerOptions::default()
+    };
+    let tokens: Vec<_> = options.configure(Lexer::new(source).with_trivia()).collect();
+    let english = keywords::translate(&tokens, &KeywordTable::english()).unwrap();
+    assert_eq!(english, "let {! nota !} var x : Integer\nin ! fin\n  while x < 3 do x := x + 1\n");
+
+    // De vuelta al español
+    let tokens: Vec<_> = Lexer::new(english.as_str()).with_trivia().with_block_comments().collect();
+    assert_eq!(
+        keywords::translate(&tokens, &Dialect::Spanish.keywords()).unwrap(),
+        "sea {! nota !} var x : Integer\nen ! fin\n  mientras x < 3 haga x := x + 1\n"
+    );
+
+    let tokens: Vec<_> = Lexer::new("let var si : Integer in si := 1").with_trivia().collect();
+    assert_eq!(
+        keywords::translate(&tokens, &Dialect::Spanish.keywords()),
+        Err("Identifier 'si' at row 1, col 9 is a keyword in the target dialect".to_string())
+    );
+}
+
+#[test]
+fn source_map_converts_between_offsets_and_positions() {
+    let map = SourceMap::new("ab\ncd");
+    assert_eq!(map.line_col(2), (1, 3));
+    assert_eq!(map.line_col(3), (2, 1));
+    assert_eq!(map.line_col(5), (2, 3));
+    assert_eq!(map.offset(1, 3), Some(2));
+    assert_eq!(map.offset(1, 4), None);
+    assert_eq!(map.offset(1, 5), None);
+    assert_eq!(map.offset(2, 3), Some(5));
+    assert_eq!(map.offset(2, 4), None);
+    assert_eq!(map.offset(3, 1), None);
+    assert_eq!(map.offset(1, 0), None);
+
+    let source = "año 𝄞\nx\n\n é";
+    let map = SourceMap::new(source);
+    let offsets = source.char_indices().map(|(offset, _)| offset).chain([source.len()]);
+    for offset in offsets {
+        let (line, col) = map.line_col(offset);
+        assert_eq!(map.offset(line, col), Some(offset), "{}:{}", line, col);
+    }
+}
+
+#[test]
+fn utf16_columns_count_surrogate_pairs() {
+    // `𝄞` ocupa 4 bytes, un carácter y dos unidades UTF-16
+    let map = SourceMap::new("'𝄞' x\né");
+    assert_eq!(map.line_col(7), (1, 5));
+    assert_eq!(map.line_col_utf16(7), (1, 6));
+    assert_eq!(map.line_col_utf16(9), (2, 1));
+
+    // Un offset dentro de un carácter da la columna de ese carácter
+    assert_eq!(map.line_col(3), (1, 2));
+    assert_eq!(map.line_col_utf16(3), (1, 2));
+    assert_eq!(SourceMap::new("é").line_col(1), (1, 1));
+}
+
+#[test]
+fn token_at_the_end_of_a_line_keeps_its_row() {
+    // Antes `Integer` salía en 2:12: la fila avanzaba al leer el salto de línea
+    let source = "let var y: Integer\nin !new year\n y := y+1";
+    let borrowed: Vec<_> = Lexer::new(source).collect();
+    let read: Vec<_> = Lexer::from_reader(source.as_bytes()).collect();
+    for tokens in [borrowed, read] {
+        let positions: Vec<_> = tokens[4..7]
+            .iter()
+            .map(|token| (token.lexeme.as_ref(), token.row, token.col))
+            .collect();
+        assert_eq!(positions, [("Integer", 1, 12), ("in", 2, 1), ("y", 3, 2)]);
+    }
+}
+
+#[test]
+fn each_lex_error_reports_its_position() {
+    let errors = |source: &str| {
+        let mut lexer = Lexer::new(source).with_block_comments().with_word_size(WordSize::Bits16);
+        lexer.by_ref().for_each(drop);
+        lexer.errors().to_vec()
+    };
+
+    assert_eq!(errors("x := 'a\ny"), [LexError::UnterminatedCharLiteral { row: 1, col: 6 }]);
+    assert_eq!(errors("x # y"), [LexError::IllegalCharacter { character: '#', row: 1, col: 3 }]);
+    assert_eq!(
+        errors("x :=\n  40000"),
+        [LexError::IntegerTooLarge { lexeme: "40000".to_string(), maxint: 32767, row: 2, col: 3 }]
+    );
+    assert_eq!(errors("x := '"), [LexError::UnexpectedEofInLiteral { row: 1, col: 6 }]);
+    assert_eq!(errors("x := '\\"), [LexError::UnexpectedEofInLiteral { row: 1, col: 6 }]);
+    assert_eq!(errors("  ''"), [LexError::EmptyCharLiteral { row: 1, col: 3 }]);
+    assert_eq!(errors("  'ab'"), [LexError::MultiCharLiteral { row: 1, col: 3 }]);
+    assert_eq!(errors("  '\\q'"), [LexError::InvalidEscape { sequence: 'q', row: 1, col: 4 }]);
+    assert_eq!(errors("x {! a {! b !}\n"), [LexError::UnterminatedComment { row: 1, col: 3 }]);
+
+    assert_eq!(
+        errors("x # y")[0].to_string(),
+        "Error at row 1, col 3: illegal character '#'"
+    );
+}
+
+#[test]
+fn tokenize_exits_with_an_error_on_lexical_errors() {
+    let dir = std::env::temp_dir().join(format!("tokenize-exit-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let tokenize = |source: &str| {
+        let (input, output) = (dir.join("entrada.tri"), dir.join("salida.tok"));
+        std::fs::write(&input, source).unwrap();
+        let result = std::process::Command::new(env!("CARGO_BIN_EXE_tokenize"))
+            .arg(&input)
+            .arg("-o")
+            .arg(&output)
+            .output()
+            .unwrap();
+        let written = std::fs::read_to_string(&output).unwrap();
+        (result.status.code(), String::from_utf8(result.stderr).unwrap(), written)
+    };
+
+    let (code, stderr, written) = tokenize("let var x : Integer in x := #");
+    assert_eq!(code, Some(1));
+    assert_eq!(stderr, "Error at row 1, col 29: illegal character '#'\n");
+    // Los tokens se escriben de todos modos
+    assert!(written.contains("{Illegal, '#', 1, 29, 28, 29}"));
+
+    let (code, stderr, _) = tokenize("let var x : Integer in x := 1");
+    assert_eq!(code, Some(0));
+    assert_eq!(stderr, "");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn operator_kind_classifies_standard_and_user_defined_operators() {
+    let kinds: Vec<_> = Lexer::new("+ // = \\= /\\ \\/ \\ <= >= <=> x")
+        .map(|token| token.operator_kind())
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            Some(OperatorKind::Plus),
+            Some(OperatorKind::Modulo),
+            Some(OperatorKind::Equal),
+            Some(OperatorKind::NotEqual),
+            Some(OperatorKind::And),
+            Some(OperatorKind::Or),
+            Some(OperatorKind::Not),
+            Some(OperatorKind::LessEqual),
+            Some(OperatorKind::GreaterEqual),
+            Some(OperatorKind::UserDefined),
+            None,
+            None,
+        ]
+    );
+}
+
+#[test]
+fn normalized_identifiers_treat_composed_and_decomposed_letters_alike() {
+    let (composed, decomposed) = ("a\u{f1}o", "an\u{303}o");
+    let lex = |source: &'static str| Lexer::new(source).with_normalized_identifiers().next().unwrap();
+
+    assert_eq!(lex(composed).lexeme, lex(decomposed).lexeme);
+    assert_eq!(lex(decomposed).lexeme, composed);
+    assert_eq!(lex(decomposed).text(), decomposed);
+    assert_eq!(lex(composed).source_text(), None);
+
+    // Sin la opción son nombres distintos
+    assert_ne!(Lexer::new(composed).next().unwrap().lexeme, Lexer::new(decomposed).next().unwrap().lexeme);
+}
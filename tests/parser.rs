@@ -0,0 +1,585 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+use compilador_rust::ast::{ASTNode, TypeDenoter, Vname};
+use compilador_rust::lexer::{LexError, Lexer};
+use compilador_rust::operators::{Associativity, OperatorTable, MAX_LEVEL};
+use compilador_rust::parser::{Parser, SyntaxError};
+use compilador_rust::token::{Token, TokenType};
+use compilador_rust::word_size::WordSize;
+
+fn parse(source: &str) -> Result<ASTNode, SyntaxError> {
+    Parser::from_lexer(Lexer::new(source)).parse()
+}
+
+fn parse_expression(source: &str, operators: OperatorTable) -> ASTNode {
+    let ast = Parser::from_lexer(Lexer::new(source))
+        .with_operators(operators)
+        .parse()
+        .unwrap();
+    match ast {
+        ASTNode::Call(_, mut args) => args.remove(0),
+        other => panic!("expected call, got {:?}", other),
+    }
+}
+
+fn binary(op: &str, left: ASTNode, right: ASTNode) -> ASTNode {
+    ASTNode::Operator(op.to_string(), Box::new(left), Box::new(right))
+}
+
+fn unary(op: &str, operand: ASTNode) -> ASTNode {
+    ASTNode::UnaryOperator(op.to_string(), Box::new(operand))
+}
+
+fn simple(name: &str) -> Vname {
+    Vname::Simple(name.to_string())
+}
+
+fn id(name: &str) -> Box<ASTNode> {
+    Box::new(ASTNode::Vname(simple(name)))
+}
+
+fn named(type_name: &str) -> TypeDenoter {
+    TypeDenoter::Named(type_name.to_string())
+}
+
+fn var(name: &str, type_name: &str) -> ASTNode {
+    ASTNode::Var(name.to_string(), named(type_name))
+}
+
+fn value_param(name: &str, type_name: &str) -> ASTNode {
+    ASTNode::ValueParameter(name.to_string(), named(type_name))
+}
+
+fn var_param(name: &str, type_name: &str) -> ASTNode {
+    ASTNode::VarParameter(name.to_string(), named(type_name))
+}
+
+#[test]
+fn parses_proc_declaration_in_let_block() {
+    let ast = parse(
+        "let
+           proc inc(var n: Integer, by: Integer) ~ n := n + by
+         in inc(x, 1)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        ast,
+        ASTNode::Let(
+            Box::new(ASTNode::Proc(
+                "inc".to_string(),
+                vec![var_param("n", "Integer"), value_param("by", "Integer")],
+                Box::new(ASTNode::Assign(
+                    simple("n"),
+                    Box::new(ASTNode::Operator("+".to_string(), id("n"), id("by"))),
+                )),
+            )),
+            Box::new(ASTNode::Call("inc".to_string(), vec![*id("x"), ASTNode::Number(1)])),
+        )
+    );
+}
+
+#[test]
+fn parses_nested_procs() {
+    let ast = parse(
+        "let
+           var total: Integer;
+           proc outer() ~
+             let
+               proc inner(k: Integer) ~ total := total + k
+             in begin inner(1); inner(2) end
+         in outer()",
+    )
+    .unwrap();
+
+    let inner = ASTNode::Proc(
+        "inner".to_string(),
+        vec![value_param("k", "Integer")],
+        Box::new(ASTNode::Assign(
+            simple("total"),
+            Box::new(ASTNode::Operator("+".to_string(), id("total"), id("k"))),
+        )),
+    );
+    let outer = ASTNode::Proc(
+        "outer".to_string(),
+        vec![],
+        Box::new(ASTNode::Let(
+            Box::new(inner),
+            Box::new(ASTNode::Command(vec![
+                ASTNode::Call("inner".to_string(), vec![ASTNode::Number(1)]),
+                ASTNode::Call("inner".to_string(), vec![ASTNode::Number(2)]),
+            ])),
+        )),
+    );
+    assert_eq!(
+        ast,
+        ASTNode::Let(
+            Box::new(ASTNode::Declaration(vec![var("total", "Integer"), outer])),
+            Box::new(ASTNode::Call("outer".to_string(), vec![])),
+        )
+    );
+}
+
+#[test]
+fn proc_declarations_mix_with_other_declarations() {
+    let ast = parse(
+        "let
+           const max ~ 10;
+           proc reset() ~ x := 0;
+           func double(n: Integer): Integer ~ n * 2
+         in reset()",
+    )
+    .unwrap();
+
+    match ast {
+        ASTNode::Let(declarations, _) => match *declarations {
+            ASTNode::Declaration(declarations) => {
+                assert_eq!(declarations.len(), 3);
+                assert!(matches!(&declarations[1], ASTNode::Proc(name, params, _) if name == "reset" && params.is_empty()));
+            }
+            other => panic!("expected a declaration sequence, got {:?}", other),
+        },
+        other => panic!("expected let, got {:?}", other),
+    }
+}
+
+#[test]
+fn proc_without_tilde_is_a_syntax_error() {
+    assert!(matches!(
+        parse("let proc p() x := 1 in p()"),
+        Err(SyntaxError::UnexpectedToken {
+            expected: TokenType::Tilde,
+            found: TokenType::Identifier,
+            row: 1,
+            col: 14,
+        })
+    ));
+}
+
+#[test]
+fn parses_type_declarations_with_array_and_record_denoters() {
+    let ast = parse(
+        "let
+           type Point ~ record x: Integer, y: Integer end;
+           type Grid ~ array 10 of array 20 of Point;
+           var origin: record p: Point, label: array 8 of Char end
+         in putint(0)",
+    )
+    .unwrap();
+
+    let point = TypeDenoter::Record(vec![
+        ("x".to_string(), named("Integer")),
+        ("y".to_string(), named("Integer")),
+    ]);
+    let grid = TypeDenoter::Array(10, Box::new(TypeDenoter::Array(20, Box::new(named("Point")))));
+    let origin = TypeDenoter::Record(vec![
+        ("p".to_string(), named("Point")),
+        ("label".to_string(), TypeDenoter::Array(8, Box::new(named("Char")))),
+    ]);
+    assert_eq!(
+        ast,
+        ASTNode::Let(
+            Box::new(ASTNode::Declaration(vec![
+                ASTNode::Type("Point".to_string(), point),
+                ASTNode::Type("Grid".to_string(), grid),
+                ASTNode::Var("origin".to_string(), origin),
+            ])),
+            Box::new(ASTNode::Call("putint".to_string(), vec![ASTNode::Number(0)])),
+        )
+    );
+}
+
+#[test]
+fn type_denoters_in_parameters_and_results() {
+    let ast = parse(
+        "let func first(v: array 3 of Integer): record n: Integer end ~ v in putint(0)",
+    )
+    .unwrap();
+
+    match ast {
+        ASTNode::Let(declaration, _) => match *declaration {
+            ASTNode::Func(_, params, result, _) => {
+                assert_eq!(
+                    params,
+                    vec![ASTNode::ValueParameter(
+                        "v".to_string(),
+                        TypeDenoter::Array(3, Box::new(named("Integer")))
+                    )]
+                );
+                assert_eq!(result, TypeDenoter::Record(vec![("n".to_string(), named("Integer"))]));
+            }
+            other => panic!("expected func, got {:?}", other),
+        },
+        other => panic!("expected let, got {:?}", other),
+    }
+}
+
+#[test]
+fn array_type_without_of_is_a_syntax_error() {
+    // Como en a.tri: `var b : array 10 true`
+    assert!(matches!(
+        parse("let var b : array 10 true in begin b := 1 end"),
+        Err(SyntaxError::UnexpectedToken {
+            expected: TokenType::Of,
+            found: TokenType::Identifier,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn parses_vnames_in_assignment_targets() {
+    let ast = parse("grid[i + 1].cells[j].x := 0").unwrap();
+
+    let target = Vname::Field(
+        Box::new(Vname::Index(
+            Box::new(Vname::Field(
+                Box::new(Vname::Index(
+                    Box::new(simple("grid")),
+                    Box::new(ASTNode::Operator("+".to_string(), id("i"), Box::new(ASTNode::Number(1)))),
+                )),
+                "cells".to_string(),
+            )),
+            id("j"),
+        )),
+        "x".to_string(),
+    );
+    assert_eq!(ast, ASTNode::Assign(target, Box::new(ASTNode::Number(0))));
+}
+
+#[test]
+fn parses_vnames_in_expressions() {
+    let ast = parse("putint(p.x + v[v[0]])").unwrap();
+
+    let field = ASTNode::Vname(Vname::Field(Box::new(simple("p")), "x".to_string()));
+    let inner = ASTNode::Vname(Vname::Index(Box::new(simple("v")), Box::new(ASTNode::Number(0))));
+    let index = ASTNode::Vname(Vname::Index(Box::new(simple("v")), Box::new(inner)));
+    assert_eq!(
+        ast,
+        ASTNode::Call(
+            "putint".to_string(),
+            vec![ASTNode::Operator("+".to_string(), Box::new(field), Box::new(index))],
+        )
+    );
+}
+
+#[test]
+fn unclosed_index_is_a_syntax_error() {
+    assert!(matches!(
+        parse("v[1 := 2"),
+        Err(SyntaxError::UnexpectedToken {
+            expected: TokenType::RightBracket,
+            found: TokenType::Assign,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn parses_array_and_record_aggregates() {
+    let ast = parse("const table ~ [{x ~ 1, y ~ 'a'}, {x ~ 2, y ~ 'b'}]").unwrap();
+
+    let entry = |x: i64, y: char| {
+        ASTNode::RecordAggregate(vec![
+            ("x".to_string(), ASTNode::Number(x)),
+            ("y".to_string(), ASTNode::Char(y)),
+        ])
+    };
+    assert_eq!(
+        ast,
+        ASTNode::Const(
+            "table".to_string(),
+            Box::new(ASTNode::ArrayAggregate(vec![entry(1, 'a'), entry(2, 'b')])),
+        )
+    );
+}
+
+#[test]
+fn parses_nested_aggregates_in_initializers() {
+    let ast = parse(
+        "let
+           const matrix ~ [[1, 2], [3, 4 + n]];
+           const origin ~ {p ~ {x ~ 0, y ~ 0}, tags ~ ['a']}
+         in putint(matrix[1][1])",
+    )
+    .unwrap();
+
+    let matrix = ASTNode::ArrayAggregate(vec![
+        ASTNode::ArrayAggregate(vec![ASTNode::Number(1), ASTNode::Number(2)]),
+        ASTNode::ArrayAggregate(vec![
+            ASTNode::Number(3),
+            ASTNode::Operator("+".to_string(), Box::new(ASTNode::Number(4)), id("n")),
+        ]),
+    ]);
+    let origin = ASTNode::RecordAggregate(vec![
+        (
+            "p".to_string(),
+            ASTNode::RecordAggregate(vec![
+                ("x".to_string(), ASTNode::Number(0)),
+                ("y".to_string(), ASTNode::Number(0)),
+            ]),
+        ),
+        ("tags".to_string(), ASTNode::ArrayAggregate(vec![ASTNode::Char('a')])),
+    ]);
+    match ast {
+        ASTNode::Let(declarations, _) => assert_eq!(
+            *declarations,
+            ASTNode::Declaration(vec![
+                ASTNode::Const("matrix".to_string(), Box::new(matrix)),
+                ASTNode::Const("origin".to_string(), Box::new(origin)),
+            ])
+        ),
+        other => panic!("expected let, got {:?}", other),
+    }
+}
+
+#[test]
+fn record_aggregate_requires_tilde() {
+    assert!(matches!(
+        parse("const r ~ {x := 1}"),
+        Err(SyntaxError::UnexpectedToken {
+            expected: TokenType::Tilde,
+            found: TokenType::Assign,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn binary_operators_follow_standard_precedence() {
+    let v = |name: &str| *id(name);
+    let operators = OperatorTable::standard;
+
+    assert_eq!(
+        parse_expression("f(a + b * c)", operators()),
+        binary("+", v("a"), binary("*", v("b"), v("c")))
+    );
+    assert_eq!(
+        parse_expression("f(a - b - c)", operators()),
+        binary("-", binary("-", v("a"), v("b")), v("c"))
+    );
+    assert_eq!(
+        parse_expression("f(a < b \\/ c = d /\\ e)", operators()),
+        binary(
+            "\\/",
+            binary("<", v("a"), v("b")),
+            binary("/\\", binary("=", v("c"), v("d")), v("e")),
+        )
+    );
+}
+
+#[test]
+fn parses_unary_operators() {
+    let v = |name: &str| *id(name);
+
+    assert_eq!(
+        parse_expression("f(-x * y)", OperatorTable::standard()),
+        binary("*", unary("-", v("x")), v("y"))
+    );
+    assert_eq!(
+        parse_expression("f(\\ \\b /\\ (a - -1))", OperatorTable::standard()),
+        binary(
+            "/\\",
+            unary("\\", unary("\\", v("b"))),
+            ASTNode::Expression(Box::new(binary("-", v("a"), unary("-", ASTNode::Number(1))))),
+        )
+    );
+}
+
+#[test]
+fn operator_table_is_configurable() {
+    let v = |name: &str| *id(name);
+
+    assert_eq!(
+        parse_expression("f(a + b * c)", OperatorTable::flat()),
+        binary("*", binary("+", v("a"), v("b")), v("c"))
+    );
+
+    let mut operators = OperatorTable::standard();
+    operators.insert("**", 6, Associativity::Right).unwrap();
+    assert_eq!(
+        parse_expression("f(a * b ** c ** d)", operators),
+        binary("*", v("a"), binary("**", v("b"), binary("**", v("c"), v("d"))))
+    );
+}
+
+#[test]
+fn highest_precedence_level_groups_left() {
+    let v = |name: &str| *id(name);
+    let mut operators = OperatorTable::standard();
+    operators.insert("**", MAX_LEVEL, Associativity::Left).unwrap();
+    assert_eq!(
+        parse_expression("f(a ** b ** c)", operators),
+        binary("**", binary("**", v("a"), v("b")), v("c"))
+    );
+}
+
+#[test]
+fn rejects_precedence_level_above_maximum() {
+    let mut operators = OperatorTable::flat();
+    assert_eq!(
+        operators.insert("**", u8::MAX, Associativity::Left),
+        Err("Operator precedence level 255 is above the maximum (254)".to_string())
+    );
+    assert!(operators.set_default_precedence(u8::MAX, Associativity::Left).is_err());
+
+    // La tabla queda como estaba
+    let v = |name: &str| *id(name);
+    assert_eq!(
+        parse_expression("f(a ** b + c)", operators),
+        binary("+", binary("**", v("a"), v("b")), v("c"))
+    );
+}
+
+#[test]
+fn parses_operator_declarations() {
+    let ast = parse(
+        "let
+            func ** (a: Integer, b: Integer) : Integer ~ a * b;
+            func ? (c: Char) : Integer ~ ord(c)
+        in
+            putint(2 ** ?'x')",
+    )
+    .unwrap();
+
+    let power = ASTNode::BinaryOperatorDeclaration(
+        "**".to_string(),
+        Box::new(value_param("a", "Integer")),
+        Box::new(value_param("b", "Integer")),
+        named("Integer"),
+        Box::new(ASTNode::Operator("*".to_string(), id("a"), id("b"))),
+    );
+    let code = ASTNode::UnaryOperatorDeclaration(
+        "?".to_string(),
+        Box::new(value_param("c", "Char")),
+        named("Integer"),
+        Box::new(ASTNode::Call("ord".to_string(), vec![*id("c")])),
+    );
+    let body = ASTNode::Call(
+        "putint".to_string(),
+        vec![binary("**", ASTNode::Number(2), unary("?", ASTNode::Char('x')))],
+    );
+    assert_eq!(
+        ast,
+        ASTNode::Let(
+            Box::new(ASTNode::Declaration(vec![power, code])),
+            Box::new(body),
+        )
+    );
+}
+
+#[test]
+fn operator_declarations_take_one_or_two_parameters() {
+    assert!(matches!(
+        parse("let func ** (a: Integer, b: Integer, c: Integer) : Integer ~ a in putint(1)"),
+        Err(SyntaxError::UnexpectedToken {
+            expected: TokenType::RightParen,
+            found: TokenType::Comma,
+            ..
+        })
+    ));
+    assert!(matches!(
+        parse("let func ** () : Integer ~ 1 in putint(1)"),
+        Err(SyntaxError::UnexpectedToken {
+            expected: TokenType::Identifier,
+            found: TokenType::RightParen,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn char_literals_must_be_a_single_character() {
+    let call = |lexeme: &'static str| {
+        Parser::new(vec![
+            Token::new(TokenType::Identifier, "put", 1, 1),
+            Token::new(TokenType::LeftParen, "(", 1, 4),
+            Token::new(TokenType::CharLiteral, lexeme, 1, 5),
+            Token::new(TokenType::RightParen, ")", 1, 8),
+            Token::new(TokenType::EOF, "", 1, 9),
+        ])
+        .parse()
+    };
+
+    assert_eq!(
+        call("𝄞").unwrap(),
+        ASTNode::Call("put".to_string(), vec![ASTNode::Char('𝄞')])
+    );
+    for lexeme in ["", "ab"] {
+        assert_eq!(
+            call(lexeme),
+            Err(SyntaxError::InvalidCharLiteral {
+                lexeme: lexeme.to_string(),
+                row: 1,
+                col: 5,
+            })
+        );
+    }
+}
+
+#[test]
+fn parser_keeps_the_lexer_errors() {
+    let mut parser = Parser::from_lexer(Lexer::new("putint(1) {! never closed").with_block_comments());
+    assert_eq!(
+        parser.parse(),
+        Ok(ASTNode::Call("putint".to_string(), vec![ASTNode::Number(1)]))
+    );
+    assert_eq!(parser.lex_errors(), [LexError::UnterminatedComment { row: 1, col: 11 }]);
+
+    let mut parser = Parser::from_lexer(Lexer::new("putint('\\q')"));
+    assert!(parser.parse().is_ok());
+    assert_eq!(
+        parser.lex_errors(),
+        [LexError::InvalidEscape { sequence: 'q', row: 1, col: 9 }]
+    );
+
+    let mut parser = Parser::from_lexer(Lexer::new("putint(1)"));
+    assert!(parser.parse().is_ok());
+    assert!(parser.lex_errors().is_empty());
+}
+
+#[test]
+fn trailing_input_after_the_program_is_a_syntax_error() {
+    assert_eq!(
+        parse("x := 1 y := 2"),
+        Err(SyntaxError::UnexpectedToken {
+            expected: TokenType::EOF,
+            found: TokenType::Identifier,
+            row: 1,
+            col: 8,
+        })
+    );
+    assert_eq!(
+        parse("begin putint(1) end end"),
+        Err(SyntaxError::UnexpectedToken {
+            expected: TokenType::EOF,
+            found: TokenType::End,
+            row: 1,
+            col: 21,
+        })
+    );
+    assert!(parse("putint(1) end").is_err());
+    assert!(parse("x := 1; y := 2 ! comentario final\n").is_ok());
+}
+
+#[test]
+fn integer_literals_above_maxint_of_the_lexer_word_size_are_out_of_range() {
+    let mut parser = Parser::from_lexer(Lexer::new("putint(40000)").with_word_size(WordSize::Bits16));
+    assert_eq!(parser.word_size(), WordSize::Bits16);
+    assert_eq!(
+        parser.parse(),
+        Err(SyntaxError::IntegerOutOfRange {
+            lexeme: "40000".to_string(),
+            maxint: 32767,
+            row: 1,
+            col: 8,
+        })
+    );
+
+    let mut parser = Parser::from_lexer(Lexer::new("putint(40000)"));
+    assert_eq!(parser.word_size(), WordSize::Bits64);
+    assert!(parser.parse().is_ok());
+}
@@ -0,0 +1,78 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+// El lexer generado a partir de `TokenSpec::triangle` debe producir exactamente
+// los mismos tokens que el `Lexer` escrito a mano.
+
+use compilador_rust::keywords::{Dialect, KeywordTable};
+use compilador_rust::lexer::Lexer;
+use compilador_rust::regex_lexer::{RegexLexer, TokenSpec};
+use compilador_rust::token::{Token, TokenType};
+
+const CORPUS: &[(&str, &str)] = &[
+    ("a.tri", include_str!("../a.tri")),
+    ("prueba.tri", include_str!("../prueba.tri")),
+    (
+        "todos los tokens",
+        "let\n  type Punto ~ record x: Integer, y: Integer end;\n  \
+         var v: array 10 of Char;\n  const c ~ '\\n'; const q ~ ''''; const r ~ '''; const b ~ '\\'\n  \
+         func f(n: Integer): Integer ~ n // 2 + (n * 3) - 4;\n  \
+         proc p(var año: Integer) ~ año := año / 2\n\
+         in begin ! comentario\n  \
+         if (a <= b) /\\ \\(c >= d) \\/ e \\= f then v[0] := 'x' else p(a);\n  \
+         while a < 10 do a := a + 1; r.x := 0; z := a == b =< c\n\
+         end\n",
+    ),
+];
+
+fn hand_written(source: &str, keywords: KeywordTable) -> Vec<Token<'_>> {
+    Lexer::new(source).with_keywords(keywords).collect()
+}
+
+fn generated<'a>(spec: &'a TokenSpec, source: &'a str) -> Vec<Token<'a>> {
+    RegexLexer::new(spec, source).collect()
+}
+
+#[test]
+fn regex_lexer_matches_hand_written_lexer_on_corpus() {
+    let spec = TokenSpec::triangle(&KeywordTable::english());
+    for (name, source) in CORPUS {
+        assert_eq!(
+            generated(&spec, source),
+            hand_written(source, KeywordTable::english()),
+            "token streams differ for {}",
+            name
+        );
+    }
+}
+
+#[test]
+fn regex_lexer_matches_hand_written_lexer_in_spanish_dialect() {
+    let source = "sea var x: Integer\nen mientras x < 10 haga si x = 0 entonces x := 1 sino x := x + 1\n";
+    let spec = TokenSpec::triangle(&Dialect::Spanish.keywords());
+    assert_eq!(
+        generated(&spec, source),
+        hand_written(source, Dialect::Spanish.keywords())
+    );
+}
+
+#[test]
+fn rules_added_first_win_ties_with_identifiers() {
+    let types = |spec: &TokenSpec| {
+        RegexLexer::new(spec, "hasta hastas")
+            .map(|token| token.token_type)
+            .collect::<Vec<_>>()
+    };
+
+    // Con `add` la regla queda después de la de identificadores y pierde el empate
+    let mut spec = TokenSpec::triangle(&KeywordTable::english());
+    spec.add(TokenType::Do, "hasta").unwrap();
+    assert_eq!(types(&spec)[0], TokenType::Identifier);
+
+    let mut spec = TokenSpec::triangle(&KeywordTable::english());
+    spec.add_first(TokenType::Do, "hasta").unwrap();
+    assert_eq!(types(&spec), [TokenType::Do, TokenType::Identifier, TokenType::EOF]);
+    assert!(spec.add_first(TokenType::Do, "(").is_err());
+}
@@ -0,0 +1,178 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+use compilador_rust::ast::ASTNode;
+use compilador_rust::lexer::Lexer;
+use compilador_rust::parser::Parser;
+use compilador_rust::resolver::{resolve_operators, OperatorBinding, ResolveError};
+use compilador_rust::token::OperatorKind;
+
+fn parse(source: &str) -> ASTNode {
+    Parser::from_lexer(Lexer::new(source)).parse().unwrap()
+}
+
+// Operador y nombre del parámetro de la declaración a la que se enlazó cada aplicación
+fn bindings(ast: &ASTNode) -> Vec<(String, Option<String>)> {
+    let resolution = resolve_operators(ast).unwrap();
+    resolution
+        .iter()
+        .map(|(application, binding)| {
+            let operator = match application {
+                ASTNode::Operator(operator, ..) | ASTNode::UnaryOperator(operator, ..) => operator,
+                other => panic!("expected operator application, got {:?}", other),
+            };
+            let declaration = match binding {
+                OperatorBinding::Standard(_) => None,
+                OperatorBinding::Declared(
+                    ASTNode::BinaryOperatorDeclaration(_, param, ..)
+                    | ASTNode::UnaryOperatorDeclaration(_, param, ..),
+                ) => match param.as_ref() {
+                    ASTNode::ValueParameter(name, _) | ASTNode::VarParameter(name, _) => Some(name.clone()),
+                    other => panic!("expected parameter, got {:?}", other),
+                },
+                OperatorBinding::Declared(other) => panic!("expected operator declaration, got {:?}", other),
+            };
+            (operator.clone(), declaration)
+        })
+        .collect()
+}
+
+#[test]
+fn standard_operators_resolve_to_the_standard_environment() {
+    let ast = parse("if \\ b /\\ (x = -1) then putint(x + 1) else putint(x // 2)");
+    let resolution = resolve_operators(&ast).unwrap();
+
+    let kinds: Vec<_> = resolution
+        .iter()
+        .map(|(_, binding)| match binding {
+            OperatorBinding::Standard(kind) => *kind,
+            OperatorBinding::Declared(declaration) => panic!("unexpected {:?}", declaration),
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            OperatorKind::And,
+            OperatorKind::Not,
+            OperatorKind::Equal,
+            OperatorKind::Minus,
+            OperatorKind::Plus,
+            OperatorKind::Modulo,
+        ]
+    );
+}
+
+#[test]
+fn applications_resolve_to_declarations_in_scope() {
+    let ast = parse(
+        "let
+            func ** (a: Integer, b: Integer) : Integer ~ if b = 0 then 1 else a * (a ** (b - 1));
+            func ** (x: Integer) : Integer ~ x * x
+        in
+            putint(2 ** 3 + ** 4)",
+    );
+
+    assert_eq!(
+        bindings(&ast),
+        vec![
+            ("=".to_string(), None),
+            ("*".to_string(), None),
+            ("**".to_string(), Some("a".to_string())),
+            ("-".to_string(), None),
+            ("*".to_string(), None),
+            ("+".to_string(), None),
+            ("**".to_string(), Some("a".to_string())),
+            ("**".to_string(), Some("x".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn equals_can_be_redeclared() {
+    let ast = parse(
+        "let
+            func = (p: Char, q: Char) : Boolean ~ ord(p) < ord(q)
+        in
+            if 'a' = 'b' then putint(1) else putint(0)",
+    );
+
+    assert_eq!(
+        bindings(&ast),
+        vec![("<".to_string(), None), ("=".to_string(), Some("p".to_string()))]
+    );
+}
+
+#[test]
+fn inner_declarations_hide_outer_ones() {
+    let ast = parse(
+        "let
+            func + (a: Integer, b: Integer) : Integer ~ a - b
+        in
+            begin
+                putint(1 + 2);
+                begin let func + (c: Integer, d: Integer) : Integer ~ c * d in putint(3 + 4) end;
+                putint(5 + 6)
+            end",
+    );
+
+    assert_eq!(
+        bindings(&ast),
+        vec![
+            ("-".to_string(), None),
+            ("+".to_string(), Some("a".to_string())),
+            ("*".to_string(), None),
+            ("+".to_string(), Some("c".to_string())),
+            ("+".to_string(), Some("a".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn undeclared_operators_are_errors() {
+    let ast = parse("let func ** (a: Integer, b: Integer) : Integer ~ a in putint(** 2)");
+    assert_eq!(
+        resolve_operators(&ast).unwrap_err(),
+        ResolveError::UndeclaredOperator {
+            operator: "**".to_string(),
+            arity: 1,
+        }
+    );
+
+    let ast = parse("putint(1 ** 2)");
+    assert_eq!(
+        resolve_operators(&ast).unwrap_err().to_string(),
+        "Undeclared binary operator: **"
+    );
+}
+
+#[test]
+fn binding_looks_up_an_application_node() {
+    let ast = parse("let func ? (c: Char) : Integer ~ ord(c) in putint(?'a')");
+    let resolution = resolve_operators(&ast).unwrap();
+
+    let (declaration, application) = match &ast {
+        ASTNode::Let(declaration, body) => match body.as_ref() {
+            ASTNode::Call(_, args) => (declaration.as_ref(), &args[0]),
+            other => panic!("expected call, got {:?}", other),
+        },
+        other => panic!("expected let, got {:?}", other),
+    };
+    assert!(matches!(
+        resolution.binding(application),
+        Some(OperatorBinding::Declared(found)) if std::ptr::eq(found, declaration)
+    ));
+    assert_eq!(resolution.binding(declaration), None);
+}
+
+#[test]
+fn binding_agrees_with_iter() {
+    let ast = parse("putint(1 + 2 * 3 - -4 // 5 + 1 + 2 + 3)");
+    let resolution = resolve_operators(&ast).unwrap();
+
+    assert_eq!(resolution.iter().count(), 8);
+    for (application, binding) in resolution.iter() {
+        assert_eq!(resolution.binding(application), Some(*binding));
+    }
+}
@@ -0,0 +1,91 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+use compilador_rust::token::{Token, TokenType};
+use compilador_rust::token_diff::{diff, Change};
+
+fn tokens(lexemes: &[&'static str]) -> Vec<Token<'static>> {
+    lexemes
+        .iter()
+        .enumerate()
+        .map(|(i, lexeme)| Token::new(TokenType::Identifier, *lexeme, 1, i + 1))
+        .collect()
+}
+
+// Cantidad mínima de inserciones y eliminaciones (por la subsecuencia común más larga)
+fn edit_distance(old: &[Token], new: &[Token]) -> usize {
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in 0..old.len() {
+        for j in 0..new.len() {
+            lcs[i + 1][j + 1] = if old[i].lexeme == new[j].lexeme {
+                lcs[i][j] + 1
+            } else {
+                lcs[i][j + 1].max(lcs[i + 1][j])
+            };
+        }
+    }
+    old.len() + new.len() - 2 * lcs[old.len()][new.len()]
+}
+
+fn edits(changes: &[Change]) -> usize {
+    changes
+        .iter()
+        .filter(|change| matches!(change, Change::Inserted(_) | Change::Deleted(_)))
+        .count()
+}
+
+#[test]
+fn reports_insertions_and_deletions_in_order() {
+    let old = tokens(&["a", "b", "c", "d"]);
+    let new = tokens(&["a", "x", "c", "d", "e"]);
+    let changes = diff(&old, &new, true);
+    assert_eq!(
+        changes,
+        vec![Change::Deleted(&old[1]), Change::Inserted(&new[1]), Change::Inserted(&new[4])]
+    );
+}
+
+#[test]
+fn reports_moved_and_retyped_tokens() {
+    let old = tokens(&["a", "b"]);
+    let mut new = tokens(&["b"]);
+    new[0].token_type = TokenType::Var;
+    let mut moved = tokens(&["x", "a"]);
+    moved.remove(0);
+
+    let changes = diff(&old, &new, false);
+    assert_eq!(changes, vec![Change::Deleted(&old[0]), Change::Retyped { old: &old[1], new: &new[0] }]);
+
+    let changes = diff(&old[..1], &moved, false);
+    assert_eq!(changes, vec![Change::Moved { old: &old[0], new: &moved[0] }]);
+    assert!(diff(&old[..1], &moved, true).is_empty());
+}
+
+#[test]
+fn alignment_is_minimal() {
+    const ALPHABET: [&str; 3] = ["a", "b", "c"];
+    // Generador congruencial para tener secuencias variadas y reproducibles
+    let mut seed = 7u32;
+    let mut next = |limit: usize| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (seed >> 16) as usize % limit
+    };
+
+    for _ in 0..500 {
+        let old: Vec<_> = (0..next(12)).map(|_| ALPHABET[next(3)]).collect();
+        let new: Vec<_> = (0..next(12)).map(|_| ALPHABET[next(3)]).collect();
+        let (old, new) = (tokens(&old), tokens(&new));
+        let changes = diff(&old, &new, true);
+        assert_eq!(edits(&changes), edit_distance(&old, &new), "{:?} -> {:?}", old, new);
+    }
+}
+
+#[test]
+fn handles_large_unrelated_inputs() {
+    let old = tokens(&["a"; 3_000]);
+    let new = tokens(&["b"; 3_000]);
+    let changes = diff(&old, &new, true);
+    assert_eq!(edits(&changes), 6_000);
+}
@@ -0,0 +1,374 @@
+// Integrantes
+// - Esteban Solano
+// - Matias Leer
+// - Melissa Carvajal
+
+use compilador_rust::lexer::Lexer;
+use compilador_rust::source_map::Span;
+use compilador_rust::token::{detokenize, Token, TokenType};
+use compilador_rust::token_file::{self, CheckError, Format, TokenFileError, TokenFilter};
+
+fn token(token_type: TokenType, lexeme: &str, row: usize, col: usize, start: usize) -> Token<'static> {
+    Token::new(token_type, lexeme.to_string(), row, col)
+        .with_span(Span::new(start, start + lexeme.len()))
+}
+
+// Lexemas que el formato tiene que escapar o que suelen romper a los lectores
+fn awkward_tokens() -> Vec<Token<'static>> {
+    vec![
+        token(TokenType::Comma, ",", 1, 1, 0),
+        token(TokenType::CharLiteral, "'", 1, 3, 2),
+        token(TokenType::Operator, "\\", 1, 7, 6),
+        token(TokenType::CharLiteral, "\n", 2, 1, 8),
+        token(TokenType::CharLiteral, "\r", 2, 6, 13),
+        token(TokenType::CharLiteral, "\t", 2, 11, 18),
+        token(TokenType::Illegal, "\u{0}", 3, 1, 23),
+        token(TokenType::Illegal, "\u{1b}", 3, 2, 24),
+        token(TokenType::Illegal, "\u{7f}", 3, 3, 25),
+        token(TokenType::Identifier, "año", 4, 1, 26).with_source_text("AN\u{303}o"),
+        token(TokenType::CharLiteral, "𝄞", 4, 5, 31),
+        token(TokenType::Illegal, "😀", 4, 9, 38),
+        token(TokenType::Illegal, "'}, {EOF, '", 5, 1, 42).with_source_text("', \"x\"\n"),
+        token(TokenType::Identifier, "vacío", 5, 20, 60).with_source_text(""),
+        token(TokenType::EOF, "", 6, 1, 53),
+    ]
+}
+
+fn write_text(tokens: &[Token]) -> String {
+    let mut out = Vec::new();
+    token_file::write_tokens(&mut out, tokens).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn text_format_round_trips_every_lexeme() {
+    let tokens = awkward_tokens();
+    let written = write_text(&tokens);
+
+    assert!(written.starts_with(&format!("#tokens v{}\n", token_file::VERSION)));
+    assert_eq!(written.lines().count(), tokens.len() + 1);
+    assert_eq!(token_file::read_tokens_from_str(&written).unwrap(), tokens);
+}
+
+#[test]
+fn text_format_escapes_lexemes() {
+    let written = write_text(&awkward_tokens());
+    let lines: Vec<&str> = written.lines().collect();
+
+    assert_eq!(lines[2], "{CharLiteral, '\\'', 1, 3, 2, 3}");
+    assert_eq!(lines[3], "{Operator, '\\\\', 1, 7, 6, 7}");
+    assert_eq!(lines[4], "{CharLiteral, '\\n', 2, 1, 8, 9}");
+    assert_eq!(lines[7], "{Illegal, '\\u{0}', 3, 1, 23, 24}");
+    assert_eq!(lines[10], "{Identifier, 'año', 4, 1, 26, 30, 'AN\u{303}o'}");
+    assert_eq!(lines[14], "{Identifier, 'vacío', 5, 20, 60, 66, ''}");
+    assert_eq!(lines[15], "{EOF, '', 6, 1, 53, 53}");
+}
+
+#[test]
+fn reads_version_2_without_source_text() {
+    let tokens = token_file::read_tokens_from_str(
+        "#tokens v2\n{Identifier, 'x', 1, 1, 0, 1}\n{CharLiteral, ',', 1, 2, 1, 4}\n",
+    )
+    .unwrap();
+    assert_eq!(
+        tokens,
+        vec![
+            token(TokenType::Identifier, "x", 1, 1, 0),
+            Token::new(TokenType::CharLiteral, ",", 1, 2).with_span(Span::new(1, 4)),
+        ]
+    );
+}
+
+#[test]
+fn reads_version_1_without_spans() {
+    let tokens = token_file::read_tokens_from_str(
+        "#tokens v1\n{Let, 'let', 1, 1}\n{CharLiteral, '\\'', 1, 5}\n{EOF, '', 2, 1}\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        tokens,
+        vec![
+            Token::new(TokenType::Let, "let", 1, 1),
+            Token::new(TokenType::CharLiteral, "'", 1, 5),
+            Token::new(TokenType::EOF, "", 2, 1),
+        ]
+    );
+}
+
+#[test]
+fn reads_legacy_files_without_header() {
+    // El formato original no tiene escapes: el lexema va tal cual entre comillas
+    let tokens = token_file::read_tokens_from_str(
+        "{Comma, ',', 1, 3}\n{CharLiteral, ''', 1, 5}\n{Operator, '\\', 2, 1}\n\n{EOF, '', 3, 1}\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        tokens,
+        vec![
+            Token::new(TokenType::Comma, ",", 1, 3),
+            Token::new(TokenType::CharLiteral, "'", 1, 5),
+            Token::new(TokenType::Operator, "\\", 2, 1),
+            Token::new(TokenType::EOF, "", 3, 1),
+        ]
+    );
+}
+
+#[test]
+fn rejects_unknown_versions_and_malformed_lines() {
+    assert!(matches!(
+        token_file::read_tokens_from_str("#tokens v99\n"),
+        Err(TokenFileError::UnsupportedVersion(version)) if version == "99"
+    ));
+    assert!(matches!(
+        token_file::read_tokens_from_str("#tokens v2\n{Let, 'let', 1, 1, 0, 3}\n{Let, 'let, 1, 5, 4, 7}\n"),
+        Err(TokenFileError::Malformed { line: 3, .. })
+    ));
+    assert!(matches!(
+        token_file::read_tokens_from_str("#tokens v2\n{Let, '\\q', 1, 1, 0, 3}\n"),
+        Err(TokenFileError::Malformed { line: 2, .. })
+    ));
+}
+
+fn write_as(tokens: &[Token], format: Format) -> String {
+    let mut out = Vec::new();
+    token_file::write_tokens_as(&mut out, tokens, format).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn json_and_csv_round_trip_every_lexeme() {
+    let tokens = awkward_tokens();
+    for format in [Format::Text, Format::Json, Format::Csv] {
+        let written = write_as(&tokens, format);
+        assert_eq!(Format::detect(&written), format);
+        assert_eq!(token_file::read_tokens_from_str(&written).unwrap(), tokens, "{:?}", format);
+    }
+}
+
+#[test]
+fn reads_json_written_by_other_tools() {
+    // Arreglo sin envoltorio, espacios arbitrarios, escapes `\/` y pares sustitutos
+    let tokens = token_file::read_tokens_from_str(
+        "[\n\t{ \"lexeme\" : \"\\/\\\\\", \"type\": \"Operator\", \"row\": 1, \"col\": 1, \"extra\": [null, true, {}] },\n\
+         {\"type\": \"CharLiteral\", \"lexeme\": \"\\ud834\\udd1e\", \"row\": 1, \"col\": 4}\n]",
+    )
+    .unwrap();
+
+    assert_eq!(
+        tokens,
+        vec![
+            Token::new(TokenType::Operator, "/\\", 1, 1),
+            Token::new(TokenType::CharLiteral, "𝄞", 1, 4),
+        ]
+    );
+}
+
+#[test]
+fn reads_json_version_1_without_spans() {
+    let tokens = token_file::read_tokens_from_str(
+        "{\"version\": 1, \"tokens\": [{\"type\": \"Let\", \"lexeme\": \"let\", \"row\": 1, \"col\": 1}]}",
+    )
+    .unwrap();
+    assert_eq!(tokens, vec![Token::new(TokenType::Let, "let", 1, 1)]);
+}
+
+#[test]
+fn rejects_malformed_json() {
+    let read = token_file::read_tokens_from_str;
+
+    assert!(matches!(
+        read("{\"version\": 99, \"tokens\": []}"),
+        Err(TokenFileError::UnsupportedVersion(version)) if version == "99"
+    ));
+    assert!(matches!(
+        read("{\"tokens\": []}"),
+        Err(TokenFileError::Malformed { message, .. }) if message.contains("version")
+    ));
+    // El error de un token indica su posición en el arreglo
+    assert!(matches!(
+        read("[{\"type\": \"Let\", \"lexeme\": \"let\", \"row\": 1, \"col\": 1},\n {\"type\": \"Let\", \"row\": 1, \"col\": 5}]"),
+        Err(TokenFileError::Malformed { line: 2, message }) if message.contains("lexeme")
+    ));
+    assert!(matches!(
+        read("[{\"type\": \"Let\", \"lexeme\": \"let\", \"row\": 1, \"col\": 1, \"start\": 0}]"),
+        Err(TokenFileError::Malformed { .. })
+    ));
+    // Los errores de sintaxis indican la línea del archivo
+    assert!(matches!(
+        read("[\n{\"type\": \"Let\",\n \"lexeme\": \"\\ud834\"}]"),
+        Err(TokenFileError::Malformed { line: 3, message }) if message.contains("surrogate")
+    ));
+    assert!(matches!(
+        read("[{\"type\": \"Let\"}] x"),
+        Err(TokenFileError::Malformed { message, .. }) if message.contains("trailing")
+    ));
+    assert!(matches!(
+        read("[{\"type\": \"Let\", \"lexeme\": \"let"),
+        Err(TokenFileError::Malformed { message, .. }) if message.contains("unterminated")
+    ));
+}
+
+#[test]
+fn csv_quotes_fields_only_when_needed() {
+    let tokens = vec![
+        token(TokenType::Identifier, "x", 1, 1, 0),
+        token(TokenType::Comma, ",", 1, 2, 1),
+        token(TokenType::Illegal, "\"", 1, 3, 2),
+        token(TokenType::CharLiteral, "\n", 1, 4, 3).with_source_text("'\\n'"),
+        token(TokenType::EOF, "", 2, 1, 7),
+    ];
+    let written = write_as(&tokens, Format::Csv);
+
+    assert_eq!(
+        written,
+        "type,lexeme,row,col,start,end,source\n\
+         Identifier,x,1,1,0,1,\n\
+         Comma,\",\",1,2,1,2,\n\
+         Illegal,\"\"\"\",1,3,2,3,\n\
+         CharLiteral,\"\n\",1,4,3,4,'\\n'\n\
+         EOF,\"\",2,1,7,7,\n"
+    );
+}
+
+#[test]
+fn reads_csv_version_2_without_source_text() {
+    let tokens = token_file::read_tokens_from_str("type,lexeme,row,col,start,end\nLet,let,1,1,0,3\n").unwrap();
+    assert_eq!(tokens, vec![token(TokenType::Let, "let", 1, 1, 0)]);
+}
+
+#[test]
+fn reads_csv_version_1_and_crlf() {
+    let tokens =
+        token_file::read_tokens_from_str("type,lexeme,row,col\r\nLet,let,1,1\r\nComma,\",\",1,5\r\n").unwrap();
+    assert_eq!(
+        tokens,
+        vec![
+            Token::new(TokenType::Let, "let", 1, 1),
+            Token::new(TokenType::Comma, ",", 1, 5),
+        ]
+    );
+}
+
+#[test]
+fn rejects_malformed_csv() {
+    let read = token_file::read_tokens_from_str;
+
+    // El error de un registro indica la línea donde empieza
+    assert!(matches!(
+        read("type,lexeme,row,col,start,end,source\nCharLiteral,\"\n\",1,1,0,1,\nLet,let,1\n"),
+        Err(TokenFileError::Malformed { line: 4, message }) if message.contains("fields")
+    ));
+    assert!(matches!(
+        read("type,lexeme,row,col,start,end,source\nLet,let,x,1,0,3,\n"),
+        Err(TokenFileError::Malformed { line: 2, message }) if message.contains("'x'")
+    ));
+    assert!(matches!(
+        read("type,lexeme,row,col,start,end,source\nLet,\"let,1,1,0,3,\n"),
+        Err(TokenFileError::Malformed { line: 2, message }) if message.contains("unterminated")
+    ));
+}
+
+#[test]
+fn source_text_of_normalized_identifiers_round_trips() {
+    let source = "let var Año : Integer in AÑO := 1";
+    let tokens: Vec<_> = Lexer::new(source).with_ignore_case().collect();
+    assert_eq!(tokens[2].lexeme, "año");
+    assert_eq!(tokens[2].source_text(), Some("Año"));
+
+    for format in [Format::Text, Format::Json, Format::Csv] {
+        let read = token_file::read_tokens_from_str(&write_as(&tokens, format)).unwrap();
+        assert_eq!(read, tokens, "{:?}", format);
+        assert_eq!(detokenize(&read).trim_end(), source);
+    }
+}
+
+#[test]
+fn detokenize_keeps_identifiers_ending_in_a_combining_mark_apart() {
+    // Caso encontrado por fuzzing: el espacio que separa `1` del identificador
+    // corre el último `1`, que quedaba pegado a la tilde combinante
+    let source = "1n\u{303}n\u{303} 1";
+    let kinds = |tokens: Vec<Token>| {
+        tokens
+            .into_iter()
+            .map(|token| (token.token_type, token.lexeme.into_owned()))
+            .collect::<Vec<_>>()
+    };
+    let tokens: Vec<_> = Lexer::new(source).collect();
+    let relexed: Vec<_> = Lexer::new(detokenize(&tokens)).collect();
+    assert_eq!(kinds(relexed), kinds(tokens));
+}
+
+#[test]
+fn check_accepts_lexer_output() {
+    let tokens: Vec<_> = Lexer::new("let var x : Integer\nin x := 1").collect();
+    assert!(token_file::check(&tokens).is_empty());
+}
+
+#[test]
+fn check_reports_missing_duplicate_and_misplaced_eof() {
+    let x = Token::new(TokenType::Identifier, "x", 1, 1);
+    let eof = Token::new(TokenType::EOF, "", 1, 2);
+
+    assert_eq!(token_file::check(std::slice::from_ref(&x)), [CheckError::MissingEof]);
+    assert_eq!(
+        token_file::check(&[x.clone(), eof.clone(), eof.clone()]),
+        [CheckError::MultipleEof { count: 2 }]
+    );
+    assert_eq!(
+        token_file::check(&[eof.clone(), x.clone()]),
+        [
+            CheckError::EofNotLast { index: 1 },
+            CheckError::PositionGoesBack { index: 2, row: 1, col: 1, previous_row: 1, previous_col: 2 },
+        ]
+    );
+    assert_eq!(CheckError::EofNotLast { index: 1 }.to_string(), "the EOF token (token 1) is not the last one");
+}
+
+#[test]
+fn check_reports_invalid_and_decreasing_positions() {
+    let x = |row, col| Token::new(TokenType::Identifier, "x", row, col);
+    let tokens = [x(1, 1), x(2, 3), x(2, 1), x(0, 4), Token::new(TokenType::EOF, "", 3, 1)];
+    assert_eq!(
+        token_file::check(&tokens),
+        [
+            CheckError::PositionGoesBack { index: 3, row: 2, col: 1, previous_row: 2, previous_col: 3 },
+            CheckError::InvalidPosition { index: 4, row: 0, col: 4 },
+            CheckError::PositionGoesBack { index: 4, row: 0, col: 4, previous_row: 2, previous_col: 1 },
+        ]
+    );
+}
+
+#[test]
+fn token_filter_selects_by_type_and_line_range() {
+    let tokens: Vec<_> = Lexer::new("let var x : Integer\nin\n  x := 1").collect();
+    let lexemes = |filter: &TokenFilter| {
+        filter
+            .apply(&tokens)
+            .iter()
+            .map(|token| token.lexeme.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(lexemes(&TokenFilter::default()).len(), tokens.len());
+    let by_type = TokenFilter { types: token_file::parse_types("Identifier, IntegerLiteral").unwrap(), lines: None };
+    assert_eq!(lexemes(&by_type), ["x", "Integer", "x", "1"]);
+    let by_line = TokenFilter { types: Vec::new(), lines: Some(token_file::parse_lines("2-3").unwrap()) };
+    assert_eq!(lexemes(&by_line), ["in", "x", ":=", "1", ""]);
+    let both = TokenFilter { lines: Some(token_file::parse_lines("3").unwrap()), ..by_type };
+    assert_eq!(lexemes(&both), ["x", "1"]);
+
+    assert_eq!(token_file::parse_lines(" 3 - 10 "), Ok(3..=10));
+    assert!(token_file::parse_lines("10-3").is_err());
+    assert!(token_file::parse_lines("3-").is_err());
+    assert!(token_file::parse_lines("x").is_err());
+    assert!(token_file::parse_types("Identifier,Foo").is_err());
+}
+
+#[test]
+fn sample_token_file_matches_the_lexer() {
+    // `salida.tok` es la salida de `tokenize prueba.tri -o salida.tok`
+    let tokens: Vec<_> = Lexer::new(include_str!("../prueba.tri")).collect();
+    assert_eq!(write_text(&tokens), include_str!("../salida.tok"));
+}